@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, BTreeSet},
     env,
     fmt::{self, Display},
     fs::File,
@@ -8,11 +8,14 @@ use std::{
     str::FromStr,
 };
 
-use tinyjson::JsonValue;
+use rosetta_i18n::provider::{DefaultProvider, LanguageProvider, PluralCategory};
 
 use crate::{
-    error::{BuildError, ConfigError},
-    gen, parser,
+    coverage::CoverageReport,
+    error::{BuildError, ConfigError, Diagnostic, DiagnosticKind, ParseError},
+    gen,
+    parser::{self, FileReport, TranslationData},
+    source::{po, Locator, SourceFile, SourceFormat},
 };
 
 /// Helper function that return an default [`RosettaBuilder`].
@@ -23,16 +26,43 @@ pub fn config() -> RosettaBuilder {
 /// Builder used to configure Rosetta code generation.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct RosettaBuilder {
-    files: HashMap<String, PathBuf>,
+    files: BTreeMap<String, (PathBuf, Option<SourceFormat>)>,
     fallback: Option<String>,
+    fallback_chains: BTreeMap<String, Vec<String>>,
+    native_names: BTreeMap<String, String>,
     name: Option<String>,
     output: Option<PathBuf>,
+    flatten_keys: bool,
+    provider: Option<ProviderConfig>,
+    diagnostics: bool,
+    strict: bool,
+    coverage_report: bool,
+    key_escaping: KeyEscaping,
+    key_enum: bool,
+    runtime_lookup: bool,
+    no_std: bool,
 }
 
 impl RosettaBuilder {
     /// Register a new translation source
+    ///
+    /// The source format is detected from the file extension (see [`SourceFormat`]).
+    /// Files with an unknown extension are read as JSON.
     pub fn source(mut self, lang: impl Into<String>, path: impl Into<String>) -> Self {
-        self.files.insert(lang.into(), PathBuf::from(path.into()));
+        self.files
+            .insert(lang.into(), (PathBuf::from(path.into()), None));
+        self
+    }
+
+    /// Register a new translation source with an explicit format
+    pub fn source_with_format(
+        mut self,
+        lang: impl Into<String>,
+        path: impl Into<String>,
+        format: SourceFormat,
+    ) -> Self {
+        self.files
+            .insert(lang.into(), (PathBuf::from(path.into()), Some(format)));
         self
     }
 
@@ -42,6 +72,51 @@ impl RosettaBuilder {
         self
     }
 
+    /// Register a fallback chain for a language
+    ///
+    /// Keys missing in the language are resolved with the languages of the chain,
+    /// in order, before the fallback language. Chains are followed transitively
+    /// and resolved at build time.
+    ///
+    /// ```no_run
+    /// rosetta_build::config()
+    ///     .source("fr-CA", "locales/fr-CA.json")
+    ///     .source("fr", "locales/fr.json")
+    ///     .source("en", "locales/en.json")
+    ///     .fallback("en")
+    ///     .fallback_chain("fr-CA", ["fr"])
+    ///     .generate();
+    /// ```
+    pub fn fallback_chain<I, S>(mut self, lang: impl Into<String>, chain: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let chain = chain.into_iter().map(Into::into).collect();
+        self.fallback_chains.insert(lang.into(), chain);
+        self
+    }
+
+    /// Set the native name of a language (e.g. `Français` for `fr`)
+    ///
+    /// The name is returned by the `native_name` method of the generated type, for
+    /// example to build a language picker. Languages without native name return their
+    /// language identifier.
+    ///
+    /// ```no_run
+    /// rosetta_build::config()
+    ///     .source("en", "locales/en.json")
+    ///     .source("fr", "locales/fr.json")
+    ///     .fallback("en")
+    ///     .native_name("en", "English")
+    ///     .native_name("fr", "Français")
+    ///     .generate();
+    /// ```
+    pub fn native_name(mut self, lang: impl Into<String>, name: impl Into<String>) -> Self {
+        self.native_names.insert(lang.into(), name.into());
+        self
+    }
+
     /// Define a custom name for the output type
     pub fn name(mut self, name: impl Into<String>) -> Self {
         self.name = Some(name.into());
@@ -54,20 +129,224 @@ impl RosettaBuilder {
         self
     }
 
+    /// Generate nested keys as flattened methods instead of accessor types
+    ///
+    /// When enabled, the `auth.login.title` key generates a `auth_login_title()` method
+    /// instead of `auth().login().title()`.
+    pub fn flatten_keys(mut self, flatten: bool) -> Self {
+        self.flatten_keys = flatten;
+        self
+    }
+
+    /// Collect all errors and warnings of translation sources before failing
+    ///
+    /// By default, code generation stops at the first error. When enabled, every file is
+    /// checked and all the problems are returned with [`BuildError::Multiple`], which is
+    /// displayed as a summary with the file, language and key of each problem.
+    ///
+    /// Returning the error from `main` prints its debug representation, so the build
+    /// script should print it itself:
+    ///
+    /// ```no_run
+    /// fn main() {
+    ///     let result = rosetta_build::config()
+    ///         .source("en", "locales/en.json")
+    ///         .source("fr", "locales/fr.json")
+    ///         .fallback("en")
+    ///         .diagnostics(true)
+    ///         .generate();
+    ///
+    ///     if let Err(error) = result {
+    ///         eprintln!("{}", error);
+    ///         std::process::exit(1);
+    ///     }
+    /// }
+    /// ```
+    #[allow(clippy::needless_doctest_main)]
+    pub fn diagnostics(mut self, enabled: bool) -> Self {
+        self.diagnostics = enabled;
+        self
+    }
+
+    /// Fail the build when translations are incomplete
+    ///
+    /// When enabled, the following problems in languages other than the fallback
+    /// are errors instead of being silently ignored or reported as warnings:
+    /// - keys missing in the language and in its [fallback chain](Self::fallback_chain)
+    /// - keys that don't exist in the fallback language
+    /// - values identical to the fallback language value (untranslated)
+    ///
+    /// This is typically enabled for release builds only, e.g. with `!cfg!(debug_assertions)`.
+    pub fn strict(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+
+    /// Escape keys that are Rust keywords or invalid identifiers
+    ///
+    /// Method names are generated by converting keys to snake case, and keys generating
+    /// a keyword (e.g. `type`) or an invalid identifier (e.g. `1st`) are errors by default.
+    ///
+    /// ```no_run
+    /// # use rosetta_build::KeyEscaping;
+    /// rosetta_build::config()
+    ///     .source("en", "locales/en.json")
+    ///     .fallback("en")
+    ///     .key_escaping(KeyEscaping::Prefix("key_".to_string()))
+    ///     .generate();
+    /// ```
+    pub fn key_escaping(mut self, escaping: KeyEscaping) -> Self {
+        self.key_escaping = escaping;
+        self
+    }
+
+    /// Generate an enum with a variant for each key
+    ///
+    /// The enum is named after the generated type (e.g. `LangKey`), and variants are
+    /// generated from flattened keys in Pascal case (e.g. `AuthLoginTitle` for `auth.login.title`).
+    /// Keys can then be stored as values and translated with `Lang::translate`, which returns
+    /// the values of keys with parameters as templates for `rosetta_i18n::lookup::format`.
+    /// Plural keys are not included in the enum.
+    ///
+    /// ```no_run
+    /// rosetta_build::config()
+    ///     .source("en", "locales/en.json")
+    ///     .fallback("en")
+    ///     .key_enum(true)
+    ///     .generate();
+    /// ```
+    pub fn key_enum(mut self, enabled: bool) -> Self {
+        self.key_enum = enabled;
+        self
+    }
+
+    /// Generate methods to look up keys from their name at runtime
+    ///
+    /// The `get` method returns the value of a key without parameters (e.g. `auth.login.title`),
+    /// and `get_formatted` formats the value of a key with parameters given by name.
+    /// Plural keys are not available through runtime lookup.
+    ///
+    /// ```no_run
+    /// rosetta_build::config()
+    ///     .source("en", "locales/en.json")
+    ///     .fallback("en")
+    ///     .runtime_lookup(true)
+    ///     .generate();
+    /// ```
+    pub fn runtime_lookup(mut self, enabled: bool) -> Self {
+        self.runtime_lookup = enabled;
+        self
+    }
+
+    /// Generate code that only depends on `core`, for `no_std` targets
+    ///
+    /// Simple keys still return a `&'static str`, and keys with parameters return a value
+    /// implementing `Display` that can be written into any `core::fmt::Write`. Plural keys
+    /// also return such a value instead of a `String`, and the `get_formatted` method of
+    /// [runtime lookup](Self::runtime_lookup) is replaced with `write_formatted`.
+    ///
+    /// The `rosetta-i18n` crate can then be used with `default-features = false`.
+    pub fn no_std(mut self, enabled: bool) -> Self {
+        self.no_std = enabled;
+        self
+    }
+
+    /// Write a translation coverage report alongside the generated code
+    ///
+    /// The report is written as `rosetta_coverage.json` and `rosetta_coverage.md` in the
+    /// [output](Self::output) directory, with the number of keys translated, missing and
+    /// identical to the fallback value, and the percentage of translated keys of each language.
+    /// Keys provided by the [fallback chain](Self::fallback_chain) of a language are not missing.
+    pub fn coverage_report(mut self, enabled: bool) -> Self {
+        self.coverage_report = enabled;
+        self
+    }
+
+    /// Use a custom language provider for plural keys
+    ///
+    /// The provider is used at build time to check that plural keys provide all the
+    /// plural categories of each language. The generated code refers to the provider
+    /// with the given `path`, which must be accessible from where translations are included.
+    ///
+    /// The [`DefaultProvider`] is used by default.
+    ///
+    /// ```no_run
+    /// # use rosetta_i18n::provider::DefaultProvider;
+    /// rosetta_build::config()
+    ///     .source("en", "locales/en.json")
+    ///     .fallback("en")
+    ///     .provider::<DefaultProvider>("::rosetta_i18n::provider::DefaultProvider")
+    ///     .generate();
+    /// ```
+    pub fn provider<P: LanguageProvider>(mut self, path: impl Into<String>) -> Self {
+        self.provider = Some(ProviderConfig::new::<P>(path.into()));
+        self
+    }
+
     /// Generate locale files and write them to the output location
     pub fn generate(self) -> Result<(), BuildError> {
         self.build()?.generate()?;
         Ok(())
     }
 
+    /// Generate code as a [`TokenStream`](proc_macro2::TokenStream) instead of writing it to a file
+    ///
+    /// This is used by the `rosetta-macros` crate. No cargo directive is printed, so the
+    /// caller is responsible for tracking changes of the source files. The
+    /// [output](Self::output) and [coverage report](Self::coverage_report) options are ignored.
+    pub fn generate_tokens(self) -> Result<proc_macro2::TokenStream, BuildError> {
+        self.build()?.generate_tokens()
+    }
+
+    /// Export the keys of the fallback language as a gettext POT template
+    ///
+    /// Keys are exported as `msgctxt` and the fallback values as `msgid`,
+    /// so that translated PO files can be registered as [sources](Self::source).
+    /// Parameters of each key are listed in a translator comment.
+    ///
+    /// ```no_run
+    /// rosetta_build::config()
+    ///     .source("en", "locales/en.json")
+    ///     .fallback("en")
+    ///     .export_pot("locales/messages.pot");
+    /// ```
+    pub fn export_pot(self, path: impl AsRef<Path>) -> Result<(), BuildError> {
+        self.build()?.export_pot(path.as_ref())
+    }
+
+    /// Export XLIFF 2.0 files to translate the keys of the fallback language
+    ///
+    /// A `{language}.xlf` file is written in the directory for each language other than
+    /// the fallback, with existing translations as targets. Parameters are encoded as
+    /// `<ph>` elements and plural keys as one unit per plural category of the language.
+    /// Translated files can then be registered as [sources](Self::source).
+    ///
+    /// ```no_run
+    /// rosetta_build::config()
+    ///     .source("en", "locales/en.json")
+    ///     .source("fr", "locales/fr.xlf")
+    ///     .fallback("en")
+    ///     .export_xliff("translations/");
+    /// ```
+    #[cfg(feature = "xliff")]
+    pub fn export_xliff(self, directory: impl AsRef<Path>) -> Result<(), BuildError> {
+        self.build()?.export_xliff(directory.as_ref())
+    }
+
     /// Validate configuration and build a [`RosettaConfig`]
     fn build(self) -> Result<RosettaConfig, ConfigError> {
-        let mut files: HashMap<LanguageId, PathBuf> = self
+        let mut files: BTreeMap<LanguageId, SourceFile> = self
             .files
             .into_iter()
-            .map(|(lang, path)| {
+            .map(|(lang, (path, format))| {
                 let lang = lang.parse::<LanguageId>()?;
-                Ok((lang, path))
+                let format = format.unwrap_or_else(|| SourceFormat::from_path(&path));
+
+                if !format.is_enabled() {
+                    return Err(ConfigError::DisabledFormat(format));
+                }
+
+                Ok((lang, SourceFile { path, format }))
             })
             .collect::<Result<_, _>>()?;
 
@@ -87,20 +366,110 @@ impl RosettaBuilder {
             None => return Err(ConfigError::MissingFallback),
         };
 
+        let fallback_chains = self
+            .fallback_chains
+            .into_iter()
+            .map(|(lang, chain)| {
+                let lang = lang.parse::<LanguageId>()?;
+                let chain = chain
+                    .iter()
+                    .map(|lang| lang.parse::<LanguageId>())
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let is_source = |lang: &LanguageId| files.contains_key(lang) || lang == &fallback.0;
+                if !is_source(&lang) || !chain.iter().all(is_source) {
+                    return Err(ConfigError::InvalidFallbackChain(lang.0));
+                }
+
+                Ok((lang, chain))
+            })
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+
+        let fallback_chains = fallback_chains
+            .keys()
+            .map(|lang| {
+                let mut resolved = Vec::new();
+                resolve_fallback_chain(lang, &fallback_chains, &mut vec![], &mut resolved)?;
+                Ok((lang.clone(), resolved))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let native_names = self
+            .native_names
+            .into_iter()
+            .map(|(lang, name)| {
+                let lang = lang.parse::<LanguageId>()?;
+                if !files.contains_key(&lang) && lang != fallback.0 {
+                    return Err(ConfigError::InvalidNativeName(lang.0));
+                }
+
+                Ok((lang, name))
+            })
+            .collect::<Result<_, _>>()?;
+
+        if let KeyEscaping::Prefix(prefix) = &self.key_escaping {
+            if prefix.is_empty() || !parser::is_identifier(&format!("{}a", prefix)) {
+                return Err(ConfigError::InvalidKeyPrefix(prefix.clone()));
+            }
+        }
+
+        let provider = self.provider.unwrap_or_default();
+        if provider.path.parse::<proc_macro2::TokenStream>().is_err() {
+            return Err(ConfigError::InvalidProvider(provider.path));
+        }
+
         Ok(RosettaConfig {
             fallback,
             others: files,
+            fallback_chains,
+            native_names,
             name: self.name.unwrap_or_else(|| "Lang".to_string()),
             output: self.output,
+            flatten_keys: self.flatten_keys,
+            diagnostics: self.diagnostics,
+            strict: self.strict,
+            coverage_report: self.coverage_report,
+            key_escaping: self.key_escaping,
+            key_enum: self.key_enum,
+            runtime_lookup: self.runtime_lookup,
+            no_std: self.no_std,
+            provider,
         })
     }
 }
 
-/// ISO 639-1 language identifier.
+/// Resolve the fallback chain of a language, following chains transitively
 ///
-/// Language identifier can be validated using the [`FromStr`] trait.
-/// It only checks if the string *looks like* a language identifier (2 character alphanumeric ascii string).
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Languages are pushed to `resolved` in resolution order, and `visiting` contains
+/// the languages of the current path to detect cycles.
+fn resolve_fallback_chain(
+    lang: &LanguageId,
+    chains: &BTreeMap<LanguageId, Vec<LanguageId>>,
+    visiting: &mut Vec<LanguageId>,
+    resolved: &mut Vec<LanguageId>,
+) -> Result<(), ConfigError> {
+    if visiting.contains(lang) {
+        return Err(ConfigError::FallbackCycle(lang.0.clone()));
+    }
+
+    visiting.push(lang.clone());
+    for next in chains.get(lang).into_iter().flatten() {
+        if !resolved.contains(next) {
+            resolved.push(next.clone());
+        }
+
+        resolve_fallback_chain(next, chains, visiting, resolved)?;
+    }
+    visiting.pop();
+
+    Ok(())
+}
+
+/// BCP 47 language tag.
+///
+/// Language identifier can be validated using the [`FromStr`] trait, which converts
+/// it to its canonical form (e.g. `pt-BR`). Validation is performed by [`rosetta_i18n::LanguageId`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub(crate) struct LanguageId(pub String);
 
 impl LanguageId {
@@ -113,13 +482,9 @@ impl FromStr for LanguageId {
     type Err = ConfigError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let valid_length = s.len() == 2;
-        let ascii_alphabetic = s.chars().all(|c| c.is_ascii_alphabetic());
-
-        if valid_length && ascii_alphabetic {
-            Ok(Self(s.to_ascii_lowercase()))
-        } else {
-            Err(ConfigError::InvalidLanguage(s.into()))
+        match rosetta_i18n::LanguageId::validate(s) {
+            Some(language_id) => Ok(Self(language_id.into_inner())),
+            None => Err(ConfigError::InvalidLanguage(s.into())),
         }
     }
 }
@@ -130,41 +495,226 @@ impl Display for LanguageId {
     }
 }
 
+/// Escaping of keys that are Rust keywords or invalid identifiers
+///
+/// See [`RosettaBuilder::key_escaping`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum KeyEscaping {
+    /// Keywords and invalid identifiers are errors (default)
+    #[default]
+    Deny,
+    /// Keywords are escaped as raw identifiers (e.g. `r#type`)
+    ///
+    /// Invalid identifiers and keywords that cannot be raw identifiers (`self`, `super`
+    /// and `crate`) are still errors.
+    Raw,
+    /// Keywords and invalid identifiers are prefixed (e.g. `key_type` and `key_1st`)
+    Prefix(String),
+}
+
+/// Language provider used for plural keys
+#[derive(Clone)]
+pub(crate) struct ProviderConfig {
+    /// Path of the provider type in generated code
+    pub path: String,
+    /// Plural categories used by a language
+    categories: fn(&rosetta_i18n::LanguageId) -> &'static [PluralCategory],
+    /// Plural category of a number in a language
+    plural: fn(&rosetta_i18n::LanguageId, u64) -> PluralCategory,
+}
+
+impl ProviderConfig {
+    fn new<P: LanguageProvider>(path: String) -> Self {
+        fn categories<P: LanguageProvider>(
+            language_id: &rosetta_i18n::LanguageId,
+        ) -> &'static [PluralCategory] {
+            P::from_id(language_id).plural_categories()
+        }
+
+        fn plural<P: LanguageProvider>(
+            language_id: &rosetta_i18n::LanguageId,
+            number: u64,
+        ) -> PluralCategory {
+            P::from_id(language_id).plural(number)
+        }
+
+        Self {
+            path,
+            categories: categories::<P>,
+            plural: plural::<P>,
+        }
+    }
+
+    /// Plural categories used by a language
+    pub fn categories(&self, language: &LanguageId) -> &'static [PluralCategory] {
+        (self.categories)(&rosetta_i18n::LanguageId::new(language.value()))
+    }
+
+    /// Plural category of a number in a language
+    pub fn plural(&self, language: &LanguageId, number: u64) -> PluralCategory {
+        (self.plural)(&rosetta_i18n::LanguageId::new(language.value()), number)
+    }
+}
+
+impl Default for ProviderConfig {
+    fn default() -> Self {
+        Self::new::<DefaultProvider>("::rosetta_i18n::provider::DefaultProvider".to_string())
+    }
+}
+
+impl fmt::Debug for ProviderConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ProviderConfig").field(&self.path).finish()
+    }
+}
+
+impl PartialEq for ProviderConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+    }
+}
+
+impl Eq for ProviderConfig {}
+
 /// Configuration for Rosetta code generation
 ///
 /// A [`RosettaBuilder`] is provided to construct and validate configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct RosettaConfig {
-    pub fallback: (LanguageId, PathBuf),
-    pub others: HashMap<LanguageId, PathBuf>,
+    pub fallback: (LanguageId, SourceFile),
+    pub others: BTreeMap<LanguageId, SourceFile>,
+    /// Resolved fallback chains of languages (without the fallback language)
+    pub fallback_chains: BTreeMap<LanguageId, Vec<LanguageId>>,
+    /// Native names of languages
+    pub native_names: BTreeMap<LanguageId, String>,
     pub name: String,
     pub output: Option<PathBuf>,
+    pub flatten_keys: bool,
+    pub diagnostics: bool,
+    pub strict: bool,
+    pub coverage_report: bool,
+    pub key_escaping: KeyEscaping,
+    pub key_enum: bool,
+    pub runtime_lookup: bool,
+    pub no_std: bool,
+    pub provider: ProviderConfig,
 }
 
 impl RosettaConfig {
-    /// Returns a list of the languages
+    /// Returns a list of the languages, sorted alphabetically
     pub fn languages(&self) -> Vec<&LanguageId> {
-        let mut languages: Vec<&LanguageId> =
-            self.others.iter().map(|(language, _)| language).collect();
+        let mut languages: Vec<&LanguageId> = self.others.keys().collect();
         languages.push(&self.fallback.0);
+        languages.sort();
         languages
     }
 
-    /// Generate locale files and write them to the output location
-    pub fn generate(&self) -> Result<(), BuildError> {
-        let fallback_content = open_file(&self.fallback.1)?;
-        let mut parsed = parser::TranslationData::from_fallback(fallback_content)?;
-        println!(
-            "cargo:rerun-if-changed={}",
-            self.fallback.1.to_string_lossy()
-        );
+    /// Parse and validate the translation sources of all languages
+    ///
+    /// In diagnostics mode, all the errors and warnings are collected
+    /// and returned with [`BuildError::Multiple`]. Cargo directives and warnings
+    /// are only printed if `build_script` is `true`.
+    fn parse(&self, build_script: bool) -> Result<TranslationData, BuildError> {
+        let mut diagnostics = Diagnostics::new(self.diagnostics, self.strict, build_script);
+        let mut locators = BTreeMap::new();
+        let (fallback, fallback_file) = &self.fallback;
 
-        for (language, path) in &self.others {
-            let content = open_file(path)?;
-            parsed.parse_file(language.clone(), content)?;
-            println!("cargo:rerun-if-changed={}", path.to_string_lossy());
+        if build_script {
+            println!(
+                "cargo:rerun-if-changed={}",
+                fallback_file.path.to_string_lossy()
+            );
         }
+        let mut parsed = match fallback_file.open(fallback, &self.provider) {
+            Ok(content) => {
+                let (parsed, mut report) = TranslationData::from_fallback_report(content.value);
+                content
+                    .errors
+                    .into_iter()
+                    .for_each(|error| report.reject(error));
+                diagnostics.rejected = report.rejected_keys.clone();
+                diagnostics.report(fallback, fallback_file, &content.locator, report)?;
+                locators.insert(fallback, content.locator);
+                Some(parsed)
+            }
+            Err(error) => {
+                diagnostics.error(fallback, fallback_file, error)?;
+                None
+            }
+        };
+
+        for (language, file) in &self.others {
+            if build_script {
+                println!("cargo:rerun-if-changed={}", file.path.to_string_lossy());
+            }
+            match file.open(language, &self.provider) {
+                Ok(content) => {
+                    if let Some(parsed) = &mut parsed {
+                        let mut report = parsed.parse_file_report(language.clone(), content.value);
+                        content
+                            .errors
+                            .into_iter()
+                            .for_each(|error| report.reject(error));
+                        diagnostics.report(language, file, &content.locator, report)?;
+                    }
+                    locators.insert(language, content.locator);
+                }
+                Err(error) => diagnostics.error(language, file, error)?,
+            }
+        }
+
+        // Errors of files that couldn't be read are not located
+        let locate = |language, error| match locators.get(language) {
+            Some(locator) => locator.locate(error),
+            None => BuildError::Parse(error),
+        };
+
+        if let Some(parsed) = &parsed {
+            let errors =
+                parsed.check_plurals(fallback, |language| self.provider.categories(language));
+
+            for error in errors {
+                let (language, file) = match &error {
+                    ParseError::MissingPluralCategories { language, .. } => self.source(language),
+                    _ => (fallback, fallback_file),
+                };
+                diagnostics.error(language, file, locate(language, error))?;
+            }
+
+            for error in gen::check_names(parsed, self) {
+                diagnostics.error(fallback, fallback_file, locate(fallback, error))?;
+            }
+
+            if self.strict {
+                for (language, file) in &self.others {
+                    let chain = self
+                        .fallback_chains
+                        .get(language)
+                        .map(Vec::as_slice)
+                        .unwrap_or_default();
+                    for error in parsed.check_translated(language, chain) {
+                        diagnostics.error(language, file, locate(language, error))?;
+                    }
+                }
+            }
+        }
+
+        diagnostics.finish()?;
+        Ok(parsed.expect("fallback errors are reported"))
+    }
+
+    /// Find the source of a language
+    fn source(&self, language: &str) -> (&LanguageId, &SourceFile) {
+        self.others
+            .iter()
+            .find(|(id, _)| id.value() == language)
+            .unwrap_or((&self.fallback.0, &self.fallback.1))
+    }
 
+    /// Generate locale files and write them to the output location
+    pub fn generate(&self) -> Result<(), BuildError> {
+        let parsed = self.parse(true)?;
         let generated = gen::CodeGenerator::new(&parsed, self).generate();
 
         let output = match &self.output {
@@ -178,28 +728,180 @@ impl RosettaConfig {
         #[cfg(feature = "rustfmt")]
         rustfmt(&output)?;
 
+        if self.coverage_report {
+            let report = CoverageReport {
+                fallback: &self.fallback.0,
+                total: parsed.keys.len(),
+                languages: self
+                    .others
+                    .keys()
+                    .map(|language| {
+                        let chain = self
+                            .fallback_chains
+                            .get(language)
+                            .map(Vec::as_slice)
+                            .unwrap_or_default();
+                        (language, parsed.coverage(language, chain))
+                    })
+                    .collect(),
+            };
+
+            let directory = output.parent().unwrap_or_else(|| Path::new(""));
+            let mut file = File::create(directory.join("rosetta_coverage.json"))?;
+            file.write_all(report.json().as_bytes())?;
+            let mut file = File::create(directory.join("rosetta_coverage.md"))?;
+            file.write_all(report.markdown().as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /// Generate code as a [`TokenStream`](proc_macro2::TokenStream)
+    pub fn generate_tokens(&self) -> Result<proc_macro2::TokenStream, BuildError> {
+        let parsed = self.parse(false)?;
+        Ok(gen::CodeGenerator::new(&parsed, self).generate())
+    }
+
+    /// Export the keys of the fallback language as a gettext POT template
+    pub fn export_pot(&self, path: &Path) -> Result<(), BuildError> {
+        let fallback_content = self.fallback.1.open(&self.fallback.0, &self.provider)?;
+        if let Some(error) = fallback_content.errors.into_iter().next() {
+            return Err(error.into());
+        }
+        let parsed = TranslationData::from_fallback(fallback_content.value)?;
+        println!(
+            "cargo:rerun-if-changed={}",
+            self.fallback.1.path.to_string_lossy()
+        );
+
+        let mut file = File::create(path)?;
+        file.write_all(po::template(&parsed).as_bytes())?;
+
+        Ok(())
+    }
+
+    /// Export a XLIFF 2.0 file for each language other than the fallback
+    #[cfg(feature = "xliff")]
+    pub fn export_xliff(&self, directory: &Path) -> Result<(), BuildError> {
+        let parsed = self.parse(true)?;
+
+        for language in self.others.keys() {
+            let content = crate::source::xliff::export(
+                &parsed,
+                &self.name,
+                &self.fallback.0,
+                language,
+                self.provider.categories(language),
+            );
+
+            let mut file = File::create(directory.join(format!("{}.xlf", language.value())))?;
+            file.write_all(content.as_bytes())?;
+        }
+
         Ok(())
     }
 }
 
-/// Open a file and read its content as a JSON [`JsonValue`]
-fn open_file(path: &Path) -> Result<JsonValue, BuildError> {
-    let content = match std::fs::read_to_string(path) {
-        Ok(content) => content,
-        Err(error) => {
-            return Err(BuildError::FileRead {
-                file: path.to_path_buf(),
-                source: error,
-            })
+/// Collector of the errors and warnings found in translation sources
+struct Diagnostics {
+    /// Whether errors are collected (diagnostics mode) or returned immediately
+    enabled: bool,
+    /// Whether unknown keys are errors (strict mode) or warnings
+    strict: bool,
+    /// Whether warnings are printed for cargo
+    build_script: bool,
+    /// Keys with errors in the fallback language, which are not unknown in other languages
+    rejected: BTreeSet<String>,
+    collected: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    fn new(enabled: bool, strict: bool, build_script: bool) -> Self {
+        Self {
+            enabled,
+            strict,
+            build_script,
+            rejected: BTreeSet::new(),
+            collected: Vec::new(),
+        }
+    }
+
+    /// Report an error, which is returned immediately if diagnostics mode is disabled
+    fn error(
+        &mut self,
+        language: &LanguageId,
+        file: &SourceFile,
+        error: BuildError,
+    ) -> Result<(), BuildError> {
+        if !self.enabled {
+            return Err(error);
         }
-    };
 
-    match content.parse::<JsonValue>() {
-        Ok(parsed) => Ok(parsed),
-        Err(error) => Err(BuildError::JsonParse {
-            file: path.to_path_buf(),
-            source: error,
-        }),
+        self.collected.push(Diagnostic {
+            file: file.path.clone(),
+            language: language.value().to_string(),
+            kind: DiagnosticKind::Error(error),
+        });
+        Ok(())
+    }
+
+    /// Report the errors and warnings of a file
+    fn report(
+        &mut self,
+        language: &LanguageId,
+        file: &SourceFile,
+        locator: &Locator,
+        report: FileReport,
+    ) -> Result<(), BuildError> {
+        let mut errors = report.errors;
+        for key in report.unknown_keys {
+            if self.rejected.contains(&key) {
+                continue;
+            }
+
+            if self.strict {
+                errors.push(ParseError::UnknownKey {
+                    key,
+                    language: language.value().to_string(),
+                });
+                continue;
+            }
+
+            if self.build_script {
+                println!(
+                    "cargo:warning=Key `{}` exists in {} but not in fallback language",
+                    key, language
+                );
+            }
+
+            if self.enabled {
+                self.collected.push(Diagnostic {
+                    file: file.path.clone(),
+                    language: language.value().to_string(),
+                    kind: DiagnosticKind::UnknownKey(key),
+                });
+            }
+        }
+
+        for error in errors {
+            self.error(language, file, locator.locate(error))?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns all the collected diagnostics if there is at least one error
+    fn finish(self) -> Result<(), BuildError> {
+        let has_errors = self
+            .collected
+            .iter()
+            .any(|diagnostic| matches!(diagnostic.kind, DiagnosticKind::Error(_)));
+
+        if has_errors {
+            Err(BuildError::Multiple(self.collected))
+        } else {
+            Ok(())
+        }
     }
 }
 
@@ -209,7 +911,7 @@ fn rustfmt(path: &Path) -> Result<(), BuildError> {
     use std::process::Command;
 
     Command::new(env::var("RUSTFMT").unwrap_or_else(|_| "rustfmt".to_string()))
-        .args(&["--emit", "files"])
+        .args(["--emit", "files"])
         .arg(path)
         .output()
         .map_err(BuildError::Fmt)?;
@@ -221,13 +923,14 @@ fn rustfmt(path: &Path) -> Result<(), BuildError> {
 mod tests {
     use super::RosettaConfig;
     use crate::{
-        builder::{LanguageId, RosettaBuilder},
-        error::ConfigError,
+        builder::{KeyEscaping, LanguageId, ProviderConfig, RosettaBuilder},
+        error::{BuildError, ConfigError},
+        source::{SourceFile, SourceFormat},
     };
 
-    use std::path::PathBuf;
+    use std::{collections::BTreeMap, path::PathBuf};
 
-    use maplit::hashmap;
+    use maplit::btreemap;
 
     #[test]
     fn config_simple() -> Result<(), Box<dyn std::error::Error>> {
@@ -240,11 +943,30 @@ mod tests {
         let expected = RosettaConfig {
             fallback: (
                 LanguageId("en".into()),
-                PathBuf::from("translations/en.json"),
+                SourceFile {
+                    path: PathBuf::from("translations/en.json"),
+                    format: SourceFormat::Json,
+                },
             ),
-            others: hashmap! { LanguageId("fr".into()) => PathBuf::from("translations/fr.json") },
+            others: btreemap! {
+                LanguageId("fr".into()) => SourceFile {
+                    path: PathBuf::from("translations/fr.json"),
+                    format: SourceFormat::Json,
+                }
+            },
+            fallback_chains: BTreeMap::new(),
+            native_names: BTreeMap::new(),
             name: "Lang".to_string(),
             output: None,
+            flatten_keys: false,
+            diagnostics: false,
+            strict: false,
+            coverage_report: false,
+            key_escaping: KeyEscaping::default(),
+            key_enum: false,
+            runtime_lookup: false,
+            no_std: false,
+            provider: ProviderConfig::default(),
         };
 
         assert_eq!(config, expected);
@@ -252,6 +974,94 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn config_regional() -> Result<(), Box<dyn std::error::Error>> {
+        let config = RosettaBuilder::default()
+            .source("pt-br", "translations/pt-BR.json")
+            .source("zh_hant", "translations/zh-Hant.json")
+            .fallback("pt-BR")
+            .build()?;
+
+        assert_eq!(config.fallback.0, LanguageId("pt-BR".into()));
+        assert!(config.others.contains_key(&LanguageId("zh-Hant".into())));
+
+        Ok(())
+    }
+
+    #[test]
+    fn config_fallback_chain() -> Result<(), Box<dyn std::error::Error>> {
+        let config = RosettaBuilder::default()
+            .source("en", "translations/en.json")
+            .source("fr", "translations/fr.json")
+            .source("fr-CA", "translations/fr-CA.json")
+            .source("fr-CH", "translations/fr-CH.json")
+            .fallback("en")
+            .fallback_chain("fr-CA", ["fr-CH"])
+            .fallback_chain("fr-CH", ["fr"])
+            .build()?;
+
+        let expected = btreemap! {
+            LanguageId("fr-CA".into()) => vec![LanguageId("fr-CH".into()), LanguageId("fr".into())],
+            LanguageId("fr-CH".into()) => vec![LanguageId("fr".into())],
+        };
+        assert_eq!(config.fallback_chains, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn config_fallback_chain_cycle() {
+        let config = RosettaBuilder::default()
+            .source("en", "translations/en.json")
+            .source("fr", "translations/fr.json")
+            .source("fr-CA", "translations/fr-CA.json")
+            .fallback("en")
+            .fallback_chain("fr-CA", ["fr"])
+            .fallback_chain("fr", ["fr-CA"])
+            .build();
+
+        assert!(matches!(config, Err(ConfigError::FallbackCycle(_))));
+    }
+
+    #[test]
+    fn config_fallback_chain_invalid() {
+        let config = RosettaBuilder::default()
+            .source("en", "translations/en.json")
+            .source("fr-CA", "translations/fr-CA.json")
+            .fallback("en")
+            .fallback_chain("fr-CA", ["fr"])
+            .build();
+
+        assert_eq!(
+            config,
+            Err(ConfigError::InvalidFallbackChain("fr-CA".to_string()))
+        );
+    }
+
+    #[test]
+    fn config_source_format() -> Result<(), Box<dyn std::error::Error>> {
+        let config = RosettaBuilder::default()
+            .source("en", "translations/en.json")
+            .source("fr", "translations/fr.yml")
+            .source_with_format("de", "translations/de.txt", SourceFormat::Yaml)
+            .fallback("en")
+            .build();
+
+        if cfg!(feature = "yaml") {
+            let config = config?;
+            let fr = &config.others[&LanguageId("fr".into())];
+            let de = &config.others[&LanguageId("de".into())];
+
+            assert_eq!(config.fallback.1.format, SourceFormat::Json);
+            assert_eq!(fr.format, SourceFormat::Yaml);
+            assert_eq!(de.format, SourceFormat::Yaml);
+        } else {
+            assert_eq!(config, Err(ConfigError::DisabledFormat(SourceFormat::Yaml)));
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn config_missing_source() {
         let config = RosettaBuilder::default().build();
@@ -292,4 +1102,68 @@ mod tests {
 
         assert_eq!(config, Err(ConfigError::InvalidFallback));
     }
+
+    #[test]
+    fn config_invalid_key_prefix() {
+        let config = RosettaBuilder::default()
+            .source("en", "translations/en.json")
+            .fallback("en")
+            .key_escaping(KeyEscaping::Prefix("1_".to_string()))
+            .build();
+
+        assert_eq!(config, Err(ConfigError::InvalidKeyPrefix("1_".to_string())));
+    }
+
+    #[test]
+    fn config_invalid_native_name() {
+        let config = RosettaBuilder::default()
+            .source("en", "translations/en.json")
+            .fallback("en")
+            .native_name("en", "English")
+            .native_name("fr", "Français")
+            .build();
+
+        assert_eq!(
+            config,
+            Err(ConfigError::InvalidNativeName("fr".to_string()))
+        );
+    }
+
+    #[test]
+    fn diagnostics_rejected_fallback_key() -> Result<(), Box<dyn std::error::Error>> {
+        let directory = std::env::temp_dir().join(format!("rosetta-build-{}", std::process::id()));
+        std::fs::create_dir_all(&directory)?;
+        std::fs::write(
+            directory.join("en.json"),
+            r#"{ "hello": "Hello {", "name": "Name" }"#,
+        )?;
+        std::fs::write(
+            directory.join("fr.json"),
+            r#"{ "hello": "Bonjour", "name": "Nom" }"#,
+        )?;
+
+        let path = |file: &str| directory.join(file).to_string_lossy().into_owned();
+
+        for strict in [false, true] {
+            let result = RosettaBuilder::default()
+                .source("en", path("en.json"))
+                .source("fr", path("fr.json"))
+                .fallback("en")
+                .diagnostics(true)
+                .strict(strict)
+                .build()?
+                .generate_tokens();
+
+            match result {
+                Err(BuildError::Multiple(diagnostics)) => {
+                    assert_eq!(diagnostics.len(), 1);
+                    assert_eq!(diagnostics[0].language, "en");
+                }
+                _ => panic!("expected diagnostics"),
+            }
+        }
+
+        std::fs::remove_dir_all(&directory)?;
+        Ok(())
+    }
 }
@@ -0,0 +1,138 @@
+//! Translation coverage report
+//!
+//! The report is written as JSON for tools (release dashboards, bots...) and as a Markdown table.
+
+use std::fmt::Write;
+
+use crate::{builder::LanguageId, parser::Coverage};
+
+/// Coverage report of all the languages other than the fallback
+pub(crate) struct CoverageReport<'a> {
+    pub(crate) fallback: &'a LanguageId,
+    /// Number of keys in the fallback language
+    pub(crate) total: usize,
+    pub(crate) languages: Vec<(&'a LanguageId, Coverage)>,
+}
+
+impl CoverageReport<'_> {
+    /// Write the report as JSON
+    pub(crate) fn json(&self) -> String {
+        let mut output = String::new();
+        writeln!(output, "{{").unwrap();
+        writeln!(output, "  \"fallback\": \"{}\",", self.fallback.value()).unwrap();
+        writeln!(output, "  \"total\": {},", self.total).unwrap();
+        write!(output, "  \"languages\": {{").unwrap();
+
+        for (i, (language, coverage)) in self.languages.iter().enumerate() {
+            let separator = if i == 0 { "" } else { "," };
+            write!(
+                output,
+                "{}\n    \"{}\": {{ \"translated\": {}, \"identical\": {}, \"missing\": {}, \"percentage\": {:.1} }}",
+                separator,
+                language.value(),
+                coverage.translated,
+                coverage.identical,
+                coverage.missing,
+                coverage.percentage()
+            )
+            .unwrap();
+        }
+
+        if !self.languages.is_empty() {
+            output.push_str("\n  ");
+        }
+        output.push_str("}\n}\n");
+
+        output
+    }
+
+    /// Write the report as a Markdown table
+    pub(crate) fn markdown(&self) -> String {
+        let mut output = String::new();
+        writeln!(
+            output,
+            "Translation coverage ({} keys, fallback language: {})\n",
+            self.total,
+            self.fallback.value()
+        )
+        .unwrap();
+        writeln!(
+            output,
+            "| Language | Translated | Identical | Missing | Complete |"
+        )
+        .unwrap();
+        writeln!(output, "|---|---:|---:|---:|---:|").unwrap();
+
+        for (language, coverage) in &self.languages {
+            writeln!(
+                output,
+                "| {} | {} | {} | {} | {:.1}% |",
+                language.value(),
+                coverage.translated,
+                coverage.identical,
+                coverage.missing,
+                coverage.percentage()
+            )
+            .unwrap();
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CoverageReport;
+    use crate::{builder::LanguageId, parser::Coverage};
+
+    #[test]
+    fn coverage_report() {
+        let (en, de, fr) = (
+            LanguageId("en".into()),
+            LanguageId("de".into()),
+            LanguageId("fr".into()),
+        );
+        let report = CoverageReport {
+            fallback: &en,
+            total: 3,
+            languages: vec![
+                (
+                    &de,
+                    Coverage {
+                        translated: 0,
+                        identical: 0,
+                        missing: 3,
+                    },
+                ),
+                (
+                    &fr,
+                    Coverage {
+                        translated: 2,
+                        identical: 1,
+                        missing: 0,
+                    },
+                ),
+            ],
+        };
+
+        let expected = r#"{
+  "fallback": "en",
+  "total": 3,
+  "languages": {
+    "de": { "translated": 0, "identical": 0, "missing": 3, "percentage": 0.0 },
+    "fr": { "translated": 2, "identical": 1, "missing": 0, "percentage": 66.7 }
+  }
+}
+"#;
+        assert_eq!(report.json(), expected);
+
+        let expected = "Translation coverage (3 keys, fallback language: en)
+
+| Language | Translated | Identical | Missing | Complete |
+|---|---:|---:|---:|---:|
+| de | 0 | 0 | 3 | 0.0% |
+| fr | 2 | 1 | 0 | 66.7% |
+";
+        assert_eq!(report.markdown(), expected);
+    }
+}
@@ -3,9 +3,11 @@
 use std::{
     error::Error,
     fmt::{self, Display},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
+use crate::source::SourceFormat;
+
 /// Error type returned when the configuration passed to [`RosettaBuilder`] is invalid.
 ///
 /// [`RosettaBuilder`]: crate::RosettaBuilder
@@ -19,6 +21,18 @@ pub enum ConfigError {
     MissingFallback,
     /// The fallback language doesn't match any source
     InvalidFallback,
+    /// The language provider path is not a valid Rust path
+    InvalidProvider(String),
+    /// A fallback chain refers to a language without source
+    InvalidFallbackChain(String),
+    /// A fallback chain contains a cycle
+    FallbackCycle(String),
+    /// The cargo feature required to read a source format is disabled
+    DisabledFormat(SourceFormat),
+    /// The prefix used to escape keys is not a valid identifier
+    InvalidKeyPrefix(String),
+    /// A native name is provided for a language without source
+    InvalidNativeName(String),
 }
 
 impl Error for ConfigError {}
@@ -37,6 +51,31 @@ impl Display for ConfigError {
                 f,
                 "no source corresponding to the fallback language was found"
             ),
+            ConfigError::InvalidProvider(path) => {
+                write!(f, "`{}` is not a valid language provider path", path)
+            }
+            ConfigError::InvalidFallbackChain(lang) => write!(
+                f,
+                "the fallback chain of `{}` refers to a language without source",
+                lang
+            ),
+            ConfigError::FallbackCycle(lang) => {
+                write!(f, "the fallback chain of `{}` contains a cycle", lang)
+            }
+            ConfigError::DisabledFormat(format) => write!(
+                f,
+                "the `{}` feature must be enabled to read {} sources",
+                format.name(),
+                format.name()
+            ),
+            ConfigError::InvalidKeyPrefix(prefix) => {
+                write!(f, "`{}` is not a valid key prefix", prefix)
+            }
+            ConfigError::InvalidNativeName(lang) => write!(
+                f,
+                "a native name is provided for `{}`, which has no source",
+                lang
+            ),
         }
     }
 }
@@ -54,7 +93,26 @@ pub enum BuildError {
         file: PathBuf,
         source: tinyjson::JsonParseError,
     },
+    PoParse {
+        file: PathBuf,
+        line: usize,
+        message: &'static str,
+    },
+    /// Syntax error in a source file of a format enabled by a cargo feature
+    SourceParse {
+        file: PathBuf,
+        line: Option<usize>,
+        message: String,
+    },
     Parse(ParseError),
+    /// Parse error located in a source file
+    Located {
+        file: PathBuf,
+        location: Location,
+        source: Box<ParseError>,
+    },
+    /// Errors and warnings collected in diagnostics mode
+    Multiple(Vec<Diagnostic>),
     Var(std::env::VarError),
     Fmt(std::io::Error),
 }
@@ -72,13 +130,138 @@ impl Display for BuildError {
             BuildError::JsonParse { file, source } => {
                 write!(f, "failed to load {:?}: {}", file, source)
             }
+            BuildError::PoParse {
+                file,
+                line,
+                message,
+            } => write!(f, "failed to load {:?} (line {}): {}", file, line, message),
+            BuildError::SourceParse {
+                file,
+                line,
+                message,
+            } => match line {
+                Some(line) => write!(f, "failed to load {:?} (line {}): {}", file, line, message),
+                None => write!(f, "failed to load {:?}: {}", file, message),
+            },
             BuildError::Parse(error) => write!(f, "failed to parse translations: {}", error),
+            BuildError::Located {
+                file,
+                location,
+                source,
+            } => {
+                write!(f, "failed to parse translations: {}", source)?;
+                location.fmt_frame(f, file)
+            }
+            BuildError::Multiple(diagnostics) => {
+                let errors = diagnostics
+                    .iter()
+                    .filter(|diagnostic| matches!(diagnostic.kind, DiagnosticKind::Error(_)))
+                    .count();
+                let warnings = diagnostics.len() - errors;
+
+                write!(
+                    f,
+                    "found {} error(s) and {} warning(s) in translation sources:",
+                    errors, warnings
+                )?;
+                for diagnostic in diagnostics {
+                    write!(f, "\n  {}", diagnostic)?;
+                }
+
+                Ok(())
+            }
             BuildError::Var(error) => write!(f, "failed to read environment variable: {}", error),
             BuildError::Fmt(error) => write!(f, "failed to run rustfmt: {}", error),
         }
     }
 }
 
+/// Error or warning found in a translation source, reported in diagnostics mode
+///
+/// See [`RosettaBuilder::diagnostics`](crate::RosettaBuilder::diagnostics).
+#[derive(Debug)]
+pub struct Diagnostic {
+    /// Path of the source file
+    pub file: PathBuf,
+    /// Language of the source file
+    pub language: String,
+    /// Error or warning
+    pub kind: DiagnosticKind,
+}
+
+/// Kind of a [`Diagnostic`]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DiagnosticKind {
+    /// Error that fails the build
+    Error(BuildError),
+    /// Key that exists in the source but not in the fallback language (warning)
+    UnknownKey(String),
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let location = format!("{} ({})", self.file.display(), self.language);
+        match &self.kind {
+            DiagnosticKind::Error(BuildError::Parse(error)) => {
+                write!(f, "error: {}: {}", location, error)
+            }
+            DiagnosticKind::Error(BuildError::Located {
+                file,
+                location: position,
+                source,
+            }) => {
+                write!(f, "error: {}: {}", location, source)?;
+                position.fmt_frame(f, file)
+            }
+            DiagnosticKind::Error(error) => write!(f, "error: {}: {}", location, error),
+            DiagnosticKind::UnknownKey(key) => write!(
+                f,
+                "warning: {}: `{}` doesn't exist in the fallback language",
+                location, key
+            ),
+        }
+    }
+}
+
+/// Position of an error in a source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// Line number, starting at 1
+    pub line: usize,
+    /// Column number (in characters), starting at 1
+    pub column: usize,
+    /// Content of the line
+    pub excerpt: String,
+}
+
+impl Location {
+    /// Write the `path:line:column` position followed by a code frame of the line
+    fn fmt_frame(&self, f: &mut fmt::Formatter<'_>, file: &Path) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        let indent: String = self
+            .excerpt
+            .chars()
+            .take(self.column - 1)
+            .map(|char| if char == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        write!(
+            f,
+            "\n{} --> {}:{}:{}\n{} |\n{} | {}\n{} | {}^",
+            gutter,
+            file.display(),
+            self.line,
+            self.column,
+            gutter,
+            self.line,
+            self.excerpt,
+            gutter,
+            indent
+        )
+    }
+}
+
 impl From<ConfigError> for BuildError {
     fn from(error: ConfigError) -> Self {
         Self::Config(error)
@@ -112,18 +295,95 @@ pub enum ParseError {
     InvalidValue { key: String },
     /// Invalid key type (doesn't match previous parsed keys)
     InvalidType { key: String, expected: &'static str },
+    /// Key defined more than once, e.g. as a dotted key and in a nested object
+    DuplicateKey { key: String },
     /// Invalid parameters supplied to interpolated key (missing and/or unknown parameters)
     InvalidParameters {
         key: String,
         missing: Vec<String>,
         unknown: Vec<String>,
     },
-    /// Invalid language identifier (not ISO 693-1 compliant)
+    /// Invalid language identifier (not BCP 47 compliant)
     InvalidLanguageId { value: String },
+    /// Plural key without all the categories required by the language
+    MissingPluralCategories {
+        key: String,
+        language: String,
+        missing: Vec<String>,
+    },
+    /// Brace that is neither escaped nor part of a parameter
+    UnescapedBrace { key: String },
+    /// Unknown type annotation of a parameter
+    InvalidParameterType {
+        key: String,
+        parameter: String,
+        value: String,
+    },
+    /// Parameter declared with different types
+    ConflictingParameterTypes {
+        key: String,
+        parameter: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// Source syntax that cannot be compiled statically
+    UnsupportedSyntax { key: String, syntax: &'static str },
+    /// Key without value in a language (strict mode)
+    MissingTranslation { key: String, language: String },
+    /// Key that doesn't exist in the fallback language (strict mode)
+    UnknownKey { key: String, language: String },
+    /// Value identical to the fallback language value (strict mode)
+    UntranslatedValue { key: String, language: String },
+    /// Placeholder whose name is not a valid parameter name
+    InvalidPlaceholder {
+        key: String,
+        placeholder: String,
+        reason: &'static str,
+    },
+    /// Placeholders with different names converted to the same parameter
+    ConflictingPlaceholders {
+        key: String,
+        placeholder: String,
+        other: String,
+        parameter: String,
+    },
+    /// Key whose method name is a Rust keyword or an invalid identifier
+    InvalidKeyName { key: String, name: String },
+    /// Keys generating the same method or type name
+    KeyCollision {
+        key: String,
+        other: String,
+        name: String,
+    },
 }
 
 impl Error for ParseError {}
 
+impl ParseError {
+    /// Key concerned by the error, if any
+    pub(crate) fn key(&self) -> Option<&str> {
+        match self {
+            ParseError::InvalidRoot | ParseError::InvalidLanguageId { .. } => None,
+            ParseError::InvalidValue { key }
+            | ParseError::InvalidType { key, .. }
+            | ParseError::DuplicateKey { key }
+            | ParseError::InvalidParameters { key, .. }
+            | ParseError::MissingPluralCategories { key, .. }
+            | ParseError::UnescapedBrace { key }
+            | ParseError::InvalidParameterType { key, .. }
+            | ParseError::ConflictingParameterTypes { key, .. }
+            | ParseError::UnsupportedSyntax { key, .. }
+            | ParseError::MissingTranslation { key, .. }
+            | ParseError::UnknownKey { key, .. }
+            | ParseError::UntranslatedValue { key, .. }
+            | ParseError::InvalidPlaceholder { key, .. }
+            | ParseError::ConflictingPlaceholders { key, .. }
+            | ParseError::InvalidKeyName { key, .. }
+            | ParseError::KeyCollision { key, .. } => Some(key),
+        }
+    }
+}
+
 impl Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -134,6 +394,11 @@ impl Display for ParseError {
                 "`{}` doesn't match previous parsed key (expected {})",
                 key, expected
             ),
+            ParseError::DuplicateKey { key } => write!(
+                f,
+                "`{}` is defined more than once (dotted keys and nested objects have the same path)",
+                key
+            ),
             ParseError::InvalidParameters {
                 key,
                 missing,
@@ -143,11 +408,86 @@ impl Display for ParseError {
                 "invalid parameters supplied to `{}` (missing: {:?}, unknown: {:?})",
                 key, missing, unknown
             ),
-            ParseError::InvalidLanguageId { value } => write!(
+            ParseError::InvalidLanguageId { value } => {
+                write!(f, "`{}` is not a valid BCP 47 language tag", value)
+            }
+            ParseError::MissingPluralCategories {
+                key,
+                language,
+                missing,
+            } => write!(
+                f,
+                "plural key `{}` is missing categories in {} (missing: {:?})",
+                key, language, missing
+            ),
+            ParseError::UnescapedBrace { key } => write!(
                 f,
-                "`{}` is not a valid ISO 693-1 language identifier",
-                value
+                "`{}` contains an unescaped brace (use `{{{{` and `}}}}` for literal braces)",
+                key
             ),
+            ParseError::InvalidParameterType {
+                key,
+                parameter,
+                value,
+            } => write!(
+                f,
+                "parameter `{}` of `{}` has an unknown type `{}`",
+                parameter, key, value
+            ),
+            ParseError::ConflictingParameterTypes {
+                key,
+                parameter,
+                expected,
+                found,
+            } => write!(
+                f,
+                "parameter `{}` of `{}` has conflicting types (expected {}, found {})",
+                parameter, key, expected, found
+            ),
+            ParseError::UnsupportedSyntax { key, syntax } => {
+                write!(f, "`{}` uses an unsupported syntax ({})", key, syntax)
+            }
+            ParseError::MissingTranslation { key, language } => {
+                write!(f, "`{}` is not translated in {}", key, language)
+            }
+            ParseError::UnknownKey { key, language } => write!(
+                f,
+                "`{}` exists in {} but not in the fallback language",
+                key, language
+            ),
+            ParseError::UntranslatedValue { key, language } => write!(
+                f,
+                "`{}` has the same value as the fallback language in {}",
+                key, language
+            ),
+            ParseError::InvalidPlaceholder {
+                key,
+                placeholder,
+                reason,
+            } => write!(
+                f,
+                "`{}` contains an invalid placeholder `{}` ({})",
+                key, placeholder, reason
+            ),
+            ParseError::ConflictingPlaceholders {
+                key,
+                placeholder,
+                other,
+                parameter,
+            } => write!(
+                f,
+                "`{}` contains the `{{{}}}` and `{{{}}}` placeholders, which are both converted to the `{}` parameter",
+                key, other, placeholder, parameter
+            ),
+            ParseError::InvalidKeyName { key, name } => write!(
+                f,
+                "`{}` generates `{}`, which is a Rust keyword or an invalid identifier \
+                (see `RosettaBuilder::key_escaping`)",
+                key, name
+            ),
+            ParseError::KeyCollision { key, other, name } => {
+                write!(f, "`{}` and `{}` both generate `{}`", key, other, name)
+            }
         }
     }
 }
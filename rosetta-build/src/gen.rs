@@ -3,7 +3,11 @@
 //! # Generated code
 //! The generated code consists of a single enum (called by default `Lang`),
 //! which expose pub(crate)lic method for each of the translation keys. These
-//! methods returns a `&'static str` where possible, otherwise a `String`.
+//! methods returns a `&'static str` where possible. Keys with parameters return
+//! a value type implementing `Display` (e.g. `LangHelloName`), and plural keys a `String`.
+//!
+//! Nested keys are exposed through accessor types (e.g. `Lang::En.auth().title()`),
+//! unless keys are flattened in the configuration (e.g. `Lang::En.auth_title()`).
 //!
 //! # Usage
 //! The code generator is contained within the [`CodeGenerator`] struct.
@@ -11,60 +15,94 @@
 //! with the generated code. Internal methods used to generate the output are not exposed.
 
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet},
     iter::FromIterator,
 };
 
 use convert_case::{Case, Casing};
-use proc_macro2::{Ident, Span, TokenStream};
-use quote::quote;
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::{format_ident, quote};
+
+use rosetta_i18n::provider::PluralCategory;
 
 use crate::{
-    builder::{LanguageId, RosettaConfig},
-    parser::{FormattedKey, SimpleKey, TranslationData, TranslationKey},
+    builder::{KeyEscaping, LanguageId, RosettaConfig},
+    error::ParseError,
+    parser::{
+        self, is_identifier, FormattedKey, ParameterType, ParameterTypes, PluralKey, SimpleKey,
+        TranslationData, TranslationKey,
+    },
 };
 
 /// Type storing state and configuration for the code generator
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct CodeGenerator<'a> {
-    keys: &'a HashMap<String, TranslationKey>,
+    keys: &'a BTreeMap<String, TranslationKey>,
     languages: Vec<&'a LanguageId>,
     fallback: &'a LanguageId,
+    fallback_chains: &'a BTreeMap<LanguageId, Vec<LanguageId>>,
+    native_names: &'a BTreeMap<LanguageId, String>,
     name: Ident,
+    flatten_keys: bool,
+    key_escaping: &'a KeyEscaping,
+    key_enum: bool,
+    runtime_lookup: bool,
+    no_std: bool,
+    provider: &'a str,
 }
 
 impl<'a> CodeGenerator<'a> {
     /// Initialize a new [`CodeGenerator`]
     pub(crate) fn new(data: &'a TranslationData, config: &'a RosettaConfig) -> Self {
         let name = Ident::new(&config.name, Span::call_site());
-
         CodeGenerator {
             keys: &data.keys,
             languages: config.languages(),
             fallback: &config.fallback.0,
+            fallback_chains: &config.fallback_chains,
+            native_names: &config.native_names,
             name,
+            flatten_keys: config.flatten_keys,
+            key_escaping: &config.key_escaping,
+            key_enum: config.key_enum,
+            runtime_lookup: config.runtime_lookup,
+            no_std: config.no_std,
+            provider: &config.provider.path,
         }
     }
 
     /// Generate code as a [`TokenStream`]
     pub(crate) fn generate(&self) -> TokenStream {
-        // Transform as PascalCase strings
-        let languages: Vec<_> = self
-            .languages
-            .iter()
-            .map(|lang| lang.value().to_case(Case::Pascal))
-            .collect();
-
         let name = &self.name;
-        let fields = languages
-            .iter()
-            .map(|lang| Ident::new(lang, Span::call_site()));
+        let fields = self.languages.iter().map(|lang| language_ident(lang));
 
         let language_impl = self.impl_language();
-        let methods = self.keys.iter().map(|(key, value)| match value {
-            TranslationKey::Simple(inner) => self.method_simple(key, inner),
-            TranslationKey::Formatted(inner) => self.method_formatted(key, inner),
+        let lookup = match self.runtime_lookup {
+            true => self.methods_lookup(),
+            false => TokenStream::new(),
+        };
+        let values = self.keys.iter().filter_map(|(key, value)| match value {
+            TranslationKey::Formatted(inner) => Some(self.value_formatted(key, inner)),
+            TranslationKey::Plural(inner) if self.no_std => Some(self.value_plural(key, inner)),
+            _ => None,
         });
+        let key_enum = match self.key_enum {
+            true => self.key_enum(),
+            false => TokenStream::new(),
+        };
+        let mut accessors = Vec::new();
+        let methods = if self.flatten_keys {
+            self.keys
+                .iter()
+                .map(|(key, value)| {
+                    let name = self.method_name(&flat_name(key));
+                    self.method(key, &name, value, &quote!(*self))
+                })
+                .collect()
+        } else {
+            let tree = KeyTree::new(self.keys);
+            self.methods_nested(&tree, &[], &quote!(*self), &mut accessors)
+        };
 
         quote! {
             /// Language type generated by the [rosetta](https://github.com/baptiste0928/rosetta) i18n library.
@@ -75,25 +113,347 @@ impl<'a> CodeGenerator<'a> {
 
             impl #name {
                 #(#methods)*
+
+                #lookup
             }
 
+            #(#accessors)*
+
+            #(#values)*
+
+            #key_enum
+
             #language_impl
         }
     }
 
-    /// Generate method for [`TranslationKey::Simple`]
-    fn method_simple(&self, key: &str, data: &SimpleKey) -> TokenStream {
-        let name = Ident::new(&key.to_case(Case::Snake), Span::call_site());
-        let fallback = &data.fallback;
-        let arms = data
-            .others
+    /// Generate methods for a node of the [`KeyTree`]
+    ///
+    /// Nested keys are accessed with an accessor type wrapping the language,
+    /// which is added to `accessors`.
+    fn methods_nested(
+        &self,
+        tree: &KeyTree,
+        path: &[&str],
+        lang: &TokenStream,
+        accessors: &mut Vec<TokenStream>,
+    ) -> Vec<TokenStream> {
+        let mut methods: Vec<_> = tree
+            .keys
+            .iter()
+            .map(|(key, value)| {
+                let full_key = path.iter().chain([key]).copied().collect::<Vec<_>>();
+                let name = self.method_name(&snake_name(key));
+                self.method(&full_key.join("."), &name, value, lang)
+            })
+            .collect();
+
+        for (group, child) in &tree.groups {
+            let mut child_path = path.to_vec();
+            child_path.push(group);
+
+            let name = &self.name;
+            let method_name = self.method_name(&snake_name(group));
+            let accessor = self.accessor_name(&child_path);
+            let child_methods = self.methods_nested(child, &child_path, &quote!(self.0), accessors);
+
+            methods.push(quote! {
+                #[allow(clippy::all)]
+                pub fn #method_name(&self) -> #accessor {
+                    #accessor(#lang)
+                }
+            });
+
+            accessors.push(quote! {
+                /// Accessor for nested translation keys generated by the [rosetta](https://github.com/baptiste0928/rosetta) i18n library.
+                #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+                pub struct #accessor(#name);
+
+                impl #accessor {
+                    #(#child_methods)*
+                }
+            });
+        }
+
+        methods
+    }
+
+    /// Name of the accessor type for a nested keys path
+    fn accessor_name(&self, path: &[&str]) -> Ident {
+        Ident::new(&accessor_name(&self.name, path), Span::call_site())
+    }
+
+    /// Identifier of a method, escaped with the configured [`KeyEscaping`]
+    fn method_name(&self, name: &str) -> Ident {
+        method_ident(name, self.key_escaping).expect("key names are checked before generation")
+    }
+
+    /// Generate method for a [`TranslationKey`]
+    ///
+    /// The `lang` parameter is the expression of the language value in the method.
+    fn method(
+        &self,
+        key: &str,
+        name: &Ident,
+        value: &TranslationKey,
+        lang: &TokenStream,
+    ) -> TokenStream {
+        match value {
+            TranslationKey::Simple(inner) => self.method_simple(name, inner, lang),
+            TranslationKey::Formatted(inner) => {
+                self.method_value(key, name, &FormattedFields::formatted(inner), lang)
+            }
+            TranslationKey::Plural(inner) if self.no_std => {
+                self.method_value(key, name, &FormattedFields::plural(inner), lang)
+            }
+            TranslationKey::Plural(inner) => self.method_plural(name, inner, lang),
+        }
+    }
+
+    /// Resolve the values of a key for each language that is not the fallback language
+    ///
+    /// Keys missing in a language are resolved with its fallback chain. Languages
+    /// that resolve to the fallback language are omitted.
+    fn resolve<'b, T>(&self, others: &'b BTreeMap<LanguageId, T>) -> Vec<(&'a LanguageId, &'b T)> {
+        self.languages
             .iter()
+            .filter(|language| **language != self.fallback)
+            .filter_map(|language| {
+                let chain = self.fallback_chains.get(*language).into_iter().flatten();
+                let value = std::iter::once(*language)
+                    .chain(chain)
+                    .take_while(|lang| *lang != self.fallback)
+                    .find_map(|lang| others.get(lang))?;
+
+                Some((*language, value))
+            })
+            .collect()
+    }
+
+    /// Resolve the value of a key in a language, with its fallback chain
+    fn value<'b, T>(
+        &self,
+        language: &LanguageId,
+        fallback: &'b T,
+        others: &'b BTreeMap<LanguageId, T>,
+    ) -> &'b T {
+        self.resolve(others)
+            .into_iter()
+            .find(|(lang, _)| *lang == language)
+            .map_or(fallback, |(_, value)| value)
+    }
+
+    /// Generate the `get` and `get_formatted` methods, to look up keys by name at runtime
+    ///
+    /// Keys are stored in a sorted table and looked up with a binary search, along with
+    /// a table of values for each language.
+    fn methods_lookup(&self) -> TokenStream {
+        let name = &self.name;
+        let simple: Vec<_> = self
+            .keys
+            .iter()
+            .filter_map(|(key, value)| match value {
+                TranslationKey::Simple(inner) => Some((key, inner)),
+                _ => None,
+            })
+            .collect();
+        let formatted: Vec<_> = self
+            .keys
+            .iter()
+            .filter_map(|(key, value)| match value {
+                TranslationKey::Formatted(inner) => Some((key, inner)),
+                _ => None,
+            })
+            .collect();
+
+        let simple_keys = simple.iter().map(|(key, _)| key);
+        let simple_arms = self.languages.iter().map(|language| {
+            let lang = language_ident(language);
+            let values = simple.iter().map(|(_, data)| {
+                parser::unescape(self.value(language, &data.fallback, &data.others).as_str())
+            });
+            quote!(#name::#lang => &[#(#values),*])
+        });
+
+        let formatted_keys = formatted.iter().map(|(key, _)| key);
+        let formatted_arms = self.languages.iter().map(|language| {
+            let lang = language_ident(language);
+            let values = formatted
+                .iter()
+                .map(|(_, data)| self.value(language, &data.fallback, &data.others));
+            quote!(#name::#lang => &[#(#values),*])
+        });
+
+        let table = quote! {
+            const KEYS: &[&str] = &[#(#formatted_keys),*];
+            let values: &[&'static str] = match self {
+                #(#formatted_arms,)*
+            };
+
+            let index = KEYS.binary_search(&key).ok()?;
+        };
+        let formatted = match self.no_std {
+            true => quote! {
+                /// Write the value of a key from its name, with parameters given by name
+                ///
+                /// Returns `None` if the key doesn't exist, is a plural key or if a parameter is missing.
+                #[allow(clippy::all)]
+                pub fn write_formatted(
+                    &self,
+                    output: &mut dyn ::core::fmt::Write,
+                    key: &str,
+                    args: &[(&str, &dyn ::core::fmt::Display)],
+                ) -> ::core::option::Option<::core::fmt::Result> {
+                    if let ::core::option::Option::Some(value) = self.get(key) {
+                        return ::core::option::Option::Some(output.write_str(value));
+                    }
+
+                    #table
+                    ::rosetta_i18n::lookup::write(output, values[index], args)
+                }
+            },
+            false => quote! {
+                /// Format the value of a key from its name, with parameters given by name
+                ///
+                /// Returns `None` if the key doesn't exist, is a plural key or if a parameter is missing.
+                #[allow(clippy::all)]
+                pub fn get_formatted(
+                    &self,
+                    key: &str,
+                    args: &[(&str, &dyn ::core::fmt::Display)],
+                ) -> ::core::option::Option<::std::string::String> {
+                    if let ::core::option::Option::Some(value) = self.get(key) {
+                        return ::core::option::Option::Some(value.to_string());
+                    }
+
+                    #table
+                    ::rosetta_i18n::lookup::format(values[index], args)
+                }
+            },
+        };
+
+        quote! {
+            /// Get the value of a key without parameters from its name (e.g. `auth.login.title`)
+            ///
+            /// Returns `None` if the key doesn't exist or has parameters.
+            #[allow(clippy::all)]
+            pub fn get(&self, key: &str) -> ::core::option::Option<&'static str> {
+                const KEYS: &[&str] = &[#(#simple_keys),*];
+                let values: &[&'static str] = match self {
+                    #(#simple_arms,)*
+                };
+
+                let index = KEYS.binary_search(&key).ok()?;
+                ::core::option::Option::Some(values[index])
+            }
+
+            #formatted
+        }
+    }
+
+    /// Generate the key enum and the `translate` method
+    ///
+    /// Variants are ordered like the keys, and their index is used to get the value
+    /// from a table of values for each language. Plural keys are skipped.
+    fn key_enum(&self) -> TokenStream {
+        let name = &self.name;
+        let enum_name = Ident::new(&key_enum_name(&name.to_string()), Span::call_site());
+        let keys: Vec<_> = self
+            .keys
+            .iter()
+            .filter(|(_, value)| !matches!(value, TranslationKey::Plural(_)))
+            .collect();
+
+        let variants: Vec<_> = keys
+            .iter()
+            .map(|(key, _)| Ident::new(&variant_name(key), Span::call_site()))
+            .collect();
+        let docs = keys.iter().map(|(key, _)| format!(" Key `{}`", key));
+        let names = keys.iter().map(|(key, _)| key);
+        let parameters = keys.iter().map(|(_, value)| match value {
+            TranslationKey::Formatted(inner) => {
+                let mut sorted = Vec::from_iter(&inner.parameters);
+                sorted.sort_by_key(|s| s.to_lowercase());
+                quote!(&[#(#sorted),*])
+            }
+            _ => quote!(&[]),
+        });
+        let indices = 0..keys.len();
+
+        let arms = self.languages.iter().map(|language| {
+            let lang = language_ident(language);
+            let values = keys.iter().map(|(_, value)| match value {
+                TranslationKey::Simple(inner) => parser::unescape(
+                    self.value(language, &inner.fallback, &inner.others)
+                        .as_str(),
+                ),
+                TranslationKey::Formatted(inner) => {
+                    self.value(language, &inner.fallback, &inner.others).clone()
+                }
+                TranslationKey::Plural(_) => unreachable!("plural keys are skipped"),
+            });
+            quote!(#name::#lang => &[#(#values),*])
+        });
+
+        quote! {
+            /// Translation key generated by the [rosetta](https://github.com/baptiste0928/rosetta) i18n library.
+            #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+            pub enum #enum_name {
+                #(#[doc = #docs] #variants),*
+            }
+
+            impl #enum_name {
+                /// Name of the key (e.g. `auth.login.title`)
+                #[allow(clippy::all)]
+                pub fn name(&self) -> &'static str {
+                    match self {
+                        #(Self::#variants => #names,)*
+                    }
+                }
+
+                /// Names of the parameters of the key, sorted alphabetically
+                #[allow(clippy::all)]
+                pub fn parameters(&self) -> &'static [&'static str] {
+                    match self {
+                        #(Self::#variants => #parameters,)*
+                    }
+                }
+            }
+
+            impl #name {
+                /// Get the value of a key
+                ///
+                /// Values of keys without parameters are returned as is. Values of keys with
+                /// parameters are returned as templates, with their `{name}` placeholders and
+                /// literal braces escaped as `{{` and `}}`, to be formatted with
+                /// `rosetta_i18n::lookup::format` (or `rosetta_i18n::lookup::write`).
+                #[allow(clippy::all, unreachable_code)]
+                pub fn translate(&self, key: #enum_name) -> &'static str {
+                    let index: usize = match key {
+                        #(#enum_name::#variants => #indices,)*
+                    };
+                    let values: &[&'static str] = match self {
+                        #(#arms,)*
+                    };
+
+                    values[index]
+                }
+            }
+        }
+    }
+
+    /// Generate method for [`TranslationKey::Simple`]
+    fn method_simple(&self, name: &Ident, data: &SimpleKey, lang: &TokenStream) -> TokenStream {
+        let fallback = parser::unescape(&data.fallback);
+        let arms = self
+            .resolve(&data.others)
+            .into_iter()
             .map(|(language, value)| self.match_arm_simple(language, value));
 
         quote! {
             #[allow(clippy::all)]
             pub fn #name(&self) -> &'static str {
-                match self {
+                match #lang {
                     #(#arms,)*
                     _ => #fallback
                 }
@@ -104,96 +464,296 @@ impl<'a> CodeGenerator<'a> {
     /// Generate match arm for [`TranslationKey::Simple`]
     fn match_arm_simple(&self, language: &LanguageId, value: &str) -> TokenStream {
         let name = &self.name;
-        let lang = Ident::new(&language.value().to_case(Case::Pascal), Span::call_site());
+        let lang = language_ident(language);
+        let value = parser::unescape(value);
 
         quote! { #name::#lang => #value }
     }
 
-    /// Generate method for [`TranslationKey::Formatted`]
-    fn method_formatted(&self, key: &str, data: &FormattedKey) -> TokenStream {
-        let name = Ident::new(&key.to_case(Case::Snake), Span::call_site());
+    /// Generate method returning the value type of a key (see [`value_type`](Self::value_type))
+    fn method_value(
+        &self,
+        key: &str,
+        name: &Ident,
+        fields: &FormattedFields,
+        lang: &TokenStream,
+    ) -> TokenStream {
+        let value_name = self.value_name(key);
+        let (params, types) = (&fields.params, &fields.types);
+        let (generics, args) = (fields.generics(true), fields.generics(false));
 
-        // Sort parameters alphabetically to have consistent ordering
-        let mut sorted = Vec::from_iter(&data.parameters);
-        sorted.sort_by_key(|s| s.to_lowercase());
-        let params = sorted
-            .iter()
-            .map(|param| Ident::new(param, Span::call_site()))
-            .map(|param| quote!(#param: impl ::std::fmt::Display));
+        quote! {
+            #[allow(clippy::all)]
+            pub fn #name<#(#generics),*>(&self, #(#params: #types),*) -> #value_name<#(#args),*> {
+                #value_name(#lang, #(#params),*)
+            }
+        }
+    }
 
-        let arms = data
-            .others
-            .iter()
-            .map(|(language, value)| self.match_arm_formatted(language, value, &data.parameters));
-        let fallback = self.format_formatted(&data.fallback, &data.parameters);
+    /// Generate the value type of a key, implementing `Display`
+    ///
+    /// The value stores the language and the parameters, and is only formatted when
+    /// displayed with the `fmt` body.
+    fn value_type(&self, key: &str, fields: &FormattedFields, fmt: TokenStream) -> TokenStream {
+        let name = &self.name;
+        let value_name = self.value_name(key);
+        let types = &fields.types;
+        let (generics, args) = (fields.generics(true), fields.generics(false));
+        let doc = format!(
+            " Formatted value of the `{}` key generated by the [rosetta](https://github.com/baptiste0928/rosetta) i18n library.",
+            key
+        );
 
         quote! {
-            #[allow(clippy::all)]
-            pub fn #name(&self, #(#params),*) -> ::std::string::String {
-                match self {
-                    #(#arms,)*
-                    _ => #fallback
+            #[doc = #doc]
+            ///
+            /// Use `to_string()` to get the value as a `String`.
+            #[derive(Debug, Clone, Copy)]
+            pub struct #value_name<#(#args),*>(#name, #(#types),*);
+
+            impl<#(#generics),*> ::core::fmt::Display for #value_name<#(#args),*> {
+                #[allow(clippy::all)]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    #fmt
                 }
             }
         }
     }
 
+    /// Name of the value type of a key
+    fn value_name(&self, key: &str) -> Ident {
+        let path: Vec<_> = key.split('.').collect();
+        self.accessor_name(&path)
+    }
+
+    /// Generate the value type of a [`TranslationKey::Formatted`]
+    fn value_formatted(&self, key: &str, data: &FormattedKey) -> TokenStream {
+        let fields = FormattedFields::formatted(data);
+        let arms = self
+            .resolve(&data.others)
+            .into_iter()
+            .map(|(language, value)| self.match_arm_formatted(language, value, &fields));
+        let fallback = self.format_formatted(&data.fallback, &fields);
+
+        let fmt = quote! {
+            match self.0 {
+                #(#arms,)*
+                _ => #fallback
+            }
+        };
+        self.value_type(key, &fields, fmt)
+    }
+
     /// Generate match arm for [`TranslationKey::Formatted`]
     fn match_arm_formatted(
         &self,
         language: &LanguageId,
         value: &str,
-        parameters: &HashSet<String>,
+        fields: &FormattedFields,
     ) -> TokenStream {
         let name = &self.name;
-        let format_value = self.format_formatted(value, parameters);
-        let lang = Ident::new(&language.value().to_case(Case::Pascal), Span::call_site());
+        let format_value = self.format_formatted(value, fields);
+        let lang = language_ident(language);
 
         quote! { #name::#lang => #format_value }
     }
 
-    /// Generate `format!` for [`TranslationKey::Formatted`]
-    fn format_formatted(&self, value: &str, parameters: &HashSet<String>) -> TokenStream {
-        let params = parameters
+    /// Generate `write!` for [`TranslationKey::Formatted`]
+    fn format_formatted(&self, value: &str, fields: &FormattedFields) -> TokenStream {
+        let params = fields.params.iter().map(|param| {
+            let field = fields.field(&param.to_string());
+            quote!(#param = #field)
+        });
+
+        quote!(::core::write!(f, #value, #(#params),*))
+    }
+
+    /// Generate the value type of a [`TranslationKey::Plural`], used in `no_std` mode
+    fn value_plural(&self, key: &str, data: &PluralKey) -> TokenStream {
+        let provider = self.provider_path();
+        let fields = FormattedFields::plural(data);
+        let arms = self
+            .resolve(&data.others)
+            .into_iter()
+            .flat_map(|(language, values)| {
+                self.match_arms_plural(Some(language), values, Some(&fields))
+            });
+        let fallback_arms = self.match_arms_plural(None, &data.fallback, Some(&fields));
+
+        let fmt = quote! {
+            let language_id = ::rosetta_i18n::Language::language_id(&self.0);
+            let provider = <#provider as ::rosetta_i18n::provider::LanguageProvider>::from_id(&language_id);
+            let category = ::rosetta_i18n::provider::LanguageProvider::plural(&provider, self.1);
+
+            match (self.0, category) {
+                #(#arms,)*
+                #(#fallback_arms),*
+            }
+        };
+        self.value_type(key, &fields, fmt)
+    }
+
+    /// Generate method for [`TranslationKey::Plural`]
+    fn method_plural(&self, name: &Ident, data: &PluralKey, lang: &TokenStream) -> TokenStream {
+        let provider = self.provider_path();
+
+        // Sort parameters alphabetically to have consistent ordering
+        let mut sorted = Vec::from_iter(&data.parameters);
+        sorted.sort_by_key(|s| s.to_lowercase());
+        let params = sorted.iter().map(|param| {
+            let ty = parameter_type(data.types.get(*param));
+            let param = Ident::new(param, Span::call_site());
+            quote!(#param: #ty)
+        });
+
+        let arms = self
+            .resolve(&data.others)
+            .into_iter()
+            .flat_map(|(language, values)| self.match_arms_plural(Some(language), values, None));
+        let fallback_arms = self.match_arms_plural(None, &data.fallback, None);
+
+        quote! {
+            #[allow(clippy::all)]
+            pub fn #name(&self, count: u64, #(#params),*) -> ::std::string::String {
+                let language_id = ::rosetta_i18n::Language::language_id(&#lang);
+                let provider = <#provider as ::rosetta_i18n::provider::LanguageProvider>::from_id(&language_id);
+                let category = ::rosetta_i18n::provider::LanguageProvider::plural(&provider, count);
+
+                match (#lang, category) {
+                    #(#arms,)*
+                    #(#fallback_arms),*
+                }
+            }
+        }
+    }
+
+    /// Path of the language provider
+    fn provider_path(&self) -> TokenStream {
+        self.provider
+            .parse()
+            .expect("provider path is validated by the builder")
+    }
+
+    /// Generate match arms for [`TranslationKey::Plural`]
+    ///
+    /// Arms match any language if `language` is `None`. The last arm matches
+    /// any category with the `other` value. See [`format_plural`](Self::format_plural)
+    /// for `fields`.
+    fn match_arms_plural(
+        &self,
+        language: Option<&LanguageId>,
+        values: &parser::PluralValues,
+        fields: Option<&FormattedFields>,
+    ) -> Vec<TokenStream> {
+        let name = &self.name;
+        let lang = match language {
+            Some(language) => {
+                let lang = language_ident(language);
+                quote!(#name::#lang)
+            }
+            None => quote!(_),
+        };
+
+        let mut arms: Vec<_> = values
             .iter()
-            .map(|param| Ident::new(param, Span::call_site()))
-            .map(|param| quote!(#param = #param));
+            .filter(|(category, _)| **category != PluralCategory::Other)
+            .map(|(category, value)| {
+                let category = category_path(*category);
+                let format_value = self.format_plural(value, fields);
+                quote!((#lang, #category) => #format_value)
+            })
+            .collect();
 
-        quote!(format!(#value, #(#params),*))
+        if let Some(value) = values.get(&PluralCategory::Other) {
+            let format_value = self.format_plural(value, fields);
+            arms.push(quote!((#lang, _) => #format_value));
+        }
+
+        arms
+    }
+
+    /// Generate `format!` for a value of [`TranslationKey::Plural`]
+    ///
+    /// Only parameters used in the value are passed to `format!`. If `fields` is set,
+    /// the value is written to the formatter with the fields of the value type instead.
+    fn format_plural(&self, value: &str, fields: Option<&FormattedFields>) -> TokenStream {
+        let params = parser::parse_parameters(value).into_iter().map(|param| {
+            let ident = Ident::new(&param, Span::call_site());
+            match fields {
+                Some(fields) => {
+                    let field = fields.field(&param);
+                    quote!(#ident = #field)
+                }
+                None => quote!(#ident = #ident),
+            }
+        });
+
+        match fields {
+            Some(_) => quote!(::core::write!(f, #value, #(#params),*)),
+            None => quote!(format!(#value, #(#params),*)),
+        }
     }
 
     /// Generate implementation for `rosetta_i18n::Language` trait.
     fn impl_language(&self) -> TokenStream {
         let name = &self.name;
-        let fallback = Ident::new(
-            &self.fallback.value().to_case(Case::Pascal),
-            Span::call_site(),
-        );
+        let fallback = language_ident(self.fallback);
+
+        let language_id_idents = self
+            .languages
+            .iter()
+            .map(|lang| (lang.value(), language_ident(lang)));
 
-        let language_id_idents = self.languages.iter().map(|lang| lang.value()).map(|lang| {
-            (
-                lang,
-                Ident::new(&lang.to_case(Case::Pascal), Span::call_site()),
-            )
+        // Languages are matched without allocating, as `LanguageId::validate` requires `alloc`
+        let from_language_id_matches = language_id_idents.clone().map(|(lang, ident)| {
+            quote! {
+                if language_id.matches(#lang) {
+                    return ::core::option::Option::Some(Self::#ident);
+                }
+            }
         });
 
-        let from_language_id_arms = language_id_idents
-            .clone()
-            .map(|(lang, ident)| quote!(#lang => ::core::option::Option::Some(Self::#ident)));
+        let to_language_id_arms = language_id_idents.clone().map(
+            |(lang, ident)| quote!(Self::#ident => ::rosetta_i18n::LanguageId::borrowed(#lang)),
+        );
 
-        let to_language_id_arms = language_id_idents
-            .map(|(lang, ident)| quote!(Self::#ident => ::rosetta_i18n::LanguageId::new(#lang)));
+        let idents: Vec<_> = language_id_idents.clone().map(|(_, ident)| ident).collect();
+        let count = idents.len();
+        let native_name_arms = self.languages.iter().map(|lang| {
+            let ident = language_ident(lang);
+            let native_name = self
+                .native_names
+                .get(*lang)
+                .map_or(lang.value(), String::as_str);
+            quote!(Self::#ident => #native_name)
+        });
 
         quote! {
+            impl #name {
+                /// All the supported languages, sorted by language identifier
+                pub const ALL: &'static [Self] = &[#(Self::#idents),*];
+                /// Number of supported languages
+                pub const COUNT: usize = #count;
+
+                /// Native name of the language (e.g. `Français`)
+                ///
+                /// Returns the language identifier if no native name is configured.
+                #[allow(clippy::all)]
+                pub fn native_name(&self) -> &'static str {
+                    match self {
+                        #(#native_name_arms,)*
+                    }
+                }
+            }
+
             impl ::rosetta_i18n::Language for #name {
                 fn from_language_id(language_id: &::rosetta_i18n::LanguageId) -> ::core::option::Option<Self> {
-                    match language_id.value() {
-                        #(#from_language_id_arms,)*
-                        _ => ::core::option::Option::None
-                    }
+                    #(#from_language_id_matches)*
+
+                    ::core::option::Option::None
                 }
 
-                fn language_id(&self) -> ::rosetta_i18n::LanguageId {
+                fn language_id(&self) -> ::rosetta_i18n::LanguageId<'_> {
                     match self {
                         #(#to_language_id_arms,)*
                     }
@@ -202,7 +762,674 @@ impl<'a> CodeGenerator<'a> {
                 fn fallback() -> Self {
                     Self::#fallback
                 }
+
+                fn all() -> &'static [Self] {
+                    Self::ALL
+                }
             }
         }
     }
 }
+
+/// Fields of the value type of a [`TranslationKey::Formatted`] (or [`TranslationKey::Plural`])
+///
+/// Parameters are sorted alphabetically. Parameters without type annotation are
+/// generic, and `&str` parameters are borrowed for the `'a` lifetime.
+struct FormattedFields {
+    params: Vec<Ident>,
+    types: Vec<TokenStream>,
+    /// Generic type parameters
+    generics: Vec<Ident>,
+    borrowed: bool,
+}
+
+impl FormattedFields {
+    fn formatted(data: &FormattedKey) -> Self {
+        Self::new(&data.parameters, &data.types, false)
+    }
+
+    /// Fields of a [`TranslationKey::Plural`], the first field being `count`
+    fn plural(data: &PluralKey) -> Self {
+        Self::new(&data.parameters, &data.types, true)
+    }
+
+    fn new(parameters: &BTreeSet<String>, types: &ParameterTypes, count: bool) -> Self {
+        let mut sorted = Vec::from_iter(parameters);
+        sorted.sort_by_key(|s| s.to_lowercase());
+
+        let mut fields = Self {
+            params: Vec::new(),
+            types: Vec::new(),
+            generics: Vec::new(),
+            borrowed: false,
+        };
+        if count {
+            fields.params.push(Ident::new("count", Span::call_site()));
+            fields.types.push(quote!(u64));
+        }
+
+        for param in sorted {
+            let ty = match types.get(param) {
+                Some(ParameterType::Str) => {
+                    fields.borrowed = true;
+                    quote!(&'a str)
+                }
+                Some(ty) => {
+                    let ty = Ident::new(ty.name(), Span::call_site());
+                    quote!(#ty)
+                }
+                None => {
+                    let generic = format_ident!("T{}", fields.generics.len());
+                    fields.generics.push(generic.clone());
+                    quote!(#generic)
+                }
+            };
+
+            fields.params.push(Ident::new(param, Span::call_site()));
+            fields.types.push(ty);
+        }
+
+        fields
+    }
+
+    /// Expression of the field of a parameter (e.g. `self.1`), the first field being the language
+    fn field(&self, param: &str) -> TokenStream {
+        let index = self
+            .params
+            .iter()
+            .position(|ident| ident == param)
+            .expect("parameters are the same in all languages");
+        let index = Literal::usize_unsuffixed(index + 1);
+
+        quote!(self.#index)
+    }
+
+    /// Generic parameters of the type, with `Display` bounds if `bounds` is true
+    fn generics(&self, bounds: bool) -> Vec<TokenStream> {
+        let lifetime = self.borrowed.then(|| quote!('a));
+        let generics = self.generics.iter().map(|generic| match bounds {
+            true => quote!(#generic: ::core::fmt::Display),
+            false => quote!(#generic),
+        });
+
+        lifetime.into_iter().chain(generics).collect()
+    }
+}
+
+/// Translation keys grouped by nesting level
+#[derive(Debug, Default)]
+struct KeyTree<'a> {
+    /// Keys at this level
+    keys: Vec<(&'a str, &'a TranslationKey)>,
+    /// Nested keys groups
+    groups: BTreeMap<&'a str, KeyTree<'a>>,
+}
+
+impl<'a> KeyTree<'a> {
+    /// Build a [`KeyTree`] from dot-separated keys
+    fn new(keys: &'a BTreeMap<String, TranslationKey>) -> Self {
+        let mut tree = Self::default();
+        for (key, value) in keys {
+            let path: Vec<_> = key.split('.').collect();
+            tree.insert(&path, value);
+        }
+
+        tree
+    }
+
+    fn insert(&mut self, path: &[&'a str], value: &'a TranslationKey) {
+        match path {
+            [] => (),
+            [key] => self.keys.push((key, value)),
+            [group, rest @ ..] => self.groups.entry(group).or_default().insert(rest, value),
+        }
+    }
+}
+
+/// Enum variant of a language (e.g. `PtBr` for `pt-BR`)
+fn language_ident(language: &LanguageId) -> Ident {
+    let variant: String = language
+        .value()
+        .split('-')
+        .map(|subtag| {
+            let (first, rest) = subtag.split_at(1);
+            first.to_ascii_uppercase() + &rest.to_ascii_lowercase()
+        })
+        .collect();
+
+    Ident::new(&variant, Span::call_site())
+}
+
+/// Type of a generated method parameter
+///
+/// Parameters without type annotation accept any type implementing `Display`.
+fn parameter_type(ty: Option<&ParameterType>) -> TokenStream {
+    match ty {
+        Some(ParameterType::Str) => quote!(&str),
+        Some(ty) => {
+            let ty = Ident::new(ty.name(), Span::call_site());
+            quote!(#ty)
+        }
+        None => quote!(impl ::core::fmt::Display),
+    }
+}
+
+/// Path of a [`PluralCategory`] in generated code
+fn category_path(category: PluralCategory) -> TokenStream {
+    let variant = Ident::new(
+        &parser::category_name(category).to_case(Case::Pascal),
+        Span::call_site(),
+    );
+    quote!(::rosetta_i18n::provider::PluralCategory::#variant)
+}
+
+/// Method name of a single key
+fn snake_name(key: &str) -> String {
+    key.to_case(Case::Snake)
+}
+
+/// Method name of a dot-separated key when keys are flattened
+fn flat_name(key: &str) -> String {
+    let parts: Vec<_> = key.split('.').map(snake_name).collect();
+    parts.join("_")
+}
+
+/// Name of the accessor type for a nested keys path
+fn accessor_name(name: &Ident, path: &[&str]) -> String {
+    let suffix: String = path.iter().map(|part| part.to_case(Case::Pascal)).collect();
+    format!("{}{}", name, suffix)
+}
+
+/// Name of the key enum
+fn key_enum_name(name: &str) -> String {
+    format!("{}Key", name)
+}
+
+/// Variant of a key in the key enum (e.g. `AuthLoginTitle` for `auth.login.title`)
+fn variant_name(key: &str) -> String {
+    flat_name(key).to_case(Case::Pascal)
+}
+
+/// Keywords that cannot be used as raw identifiers
+const NON_RAW_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+
+/// Identifier of a method name, escaped if it is a keyword or an invalid identifier
+///
+/// Returns `None` if the name cannot be escaped with the given [`KeyEscaping`].
+fn method_ident(name: &str, escaping: &KeyEscaping) -> Option<Ident> {
+    if is_identifier(name) {
+        return Some(Ident::new(name, Span::call_site()));
+    }
+
+    match escaping {
+        KeyEscaping::Deny => None,
+        KeyEscaping::Raw
+            if parser::KEYWORDS.contains(&name) && !NON_RAW_KEYWORDS.contains(&name) =>
+        {
+            Some(Ident::new_raw(name, Span::call_site()))
+        }
+        KeyEscaping::Raw => None,
+        KeyEscaping::Prefix(prefix) => {
+            let name = format!("{}{}", prefix, name);
+            is_identifier(&name).then(|| Ident::new(&name, Span::call_site()))
+        }
+    }
+}
+
+/// Check that the keys generate valid and unique method and type names
+///
+/// Keys that differ only by their case (e.g. `helloWorld` and `hello_world`) generate
+/// the same method name, and keys that are Rust keywords can only be used with a [`KeyEscaping`].
+pub(crate) fn check_names(data: &TranslationData, config: &RosettaConfig) -> Vec<ParseError> {
+    let mut checker = NameChecker {
+        escaping: &config.key_escaping,
+        methods: generated_methods(config),
+        errors: Vec::new(),
+    };
+
+    let name = Ident::new(&config.name, Span::call_site());
+    let mut types = BTreeMap::new();
+    if config.key_enum {
+        let enum_name = key_enum_name(&config.name);
+        types.insert(enum_name.clone(), enum_name);
+    }
+
+    if config.flatten_keys {
+        let mut names = checker.methods.clone();
+        for key in data.keys.keys() {
+            checker.check(&mut names, key, &flat_name(key));
+        }
+    } else {
+        checker.check_tree(&KeyTree::new(&data.keys), &[], &name, &mut types);
+    }
+
+    checker.check_values(&data.keys, &name, config.no_std, &mut types);
+    if config.key_enum {
+        checker.check_variants(&data.keys);
+    }
+
+    checker.errors
+}
+
+/// Methods generated on the language type, that cannot be used as key names
+const GENERATED_METHODS: &[&str] = &["native_name"];
+
+/// Names of the methods generated on the language type, mapped to their path
+fn generated_methods(config: &RosettaConfig) -> BTreeMap<String, String> {
+    let formatted = match config.no_std {
+        true => "write_formatted",
+        false => "get_formatted",
+    };
+    let lookup = match config.runtime_lookup {
+        true => vec!["get", formatted],
+        false => Vec::new(),
+    };
+    let key_enum = config.key_enum.then_some("translate");
+
+    GENERATED_METHODS
+        .iter()
+        .copied()
+        .chain(lookup)
+        .chain(key_enum)
+        .map(|method| (method.to_string(), format!("{}::{}", config.name, method)))
+        .collect()
+}
+
+struct NameChecker<'a> {
+    escaping: &'a KeyEscaping,
+    /// Methods generated on the language type
+    methods: BTreeMap<String, String>,
+    errors: Vec<ParseError>,
+}
+
+impl NameChecker<'_> {
+    /// Check the method name of a key, `names` being the other names of the same type
+    ///
+    /// Returns `false` if an error was found.
+    fn check(&mut self, names: &mut BTreeMap<String, String>, key: &str, name: &str) -> bool {
+        let ident = match method_ident(name, self.escaping) {
+            Some(ident) => ident.to_string(),
+            None => {
+                self.errors.push(ParseError::InvalidKeyName {
+                    key: key.to_string(),
+                    name: name.to_string(),
+                });
+                return false;
+            }
+        };
+
+        match names.get(&ident) {
+            Some(other) => {
+                self.errors.push(ParseError::KeyCollision {
+                    key: key.to_string(),
+                    other: other.clone(),
+                    name: ident,
+                });
+                false
+            }
+            None => {
+                names.insert(ident, key.to_string());
+                true
+            }
+        }
+    }
+
+    /// Check that the accessor type of a nested key is unique
+    fn check_accessor(
+        &mut self,
+        accessors: &mut BTreeMap<String, String>,
+        key: String,
+        accessor: String,
+    ) {
+        match accessors.get(&accessor) {
+            Some(other) => self.errors.push(ParseError::KeyCollision {
+                key,
+                other: other.clone(),
+                name: accessor,
+            }),
+            None => {
+                accessors.insert(accessor, key);
+            }
+        }
+    }
+
+    /// Check that the value types of formatted keys (and plural keys if `plural`) are unique
+    fn check_values(
+        &mut self,
+        keys: &BTreeMap<String, TranslationKey>,
+        name: &Ident,
+        plural: bool,
+        types: &mut BTreeMap<String, String>,
+    ) {
+        let reported: BTreeSet<_> = self.errors.iter().filter_map(ParseError::key).collect();
+        let keys: Vec<_> = keys
+            .iter()
+            .filter(|(key, value)| {
+                let value_type = match value {
+                    TranslationKey::Simple(_) => false,
+                    TranslationKey::Formatted(_) => true,
+                    TranslationKey::Plural(_) => plural,
+                };
+                value_type && !reported.contains(key.as_str())
+            })
+            .map(|(key, _)| key)
+            .collect();
+
+        for key in keys {
+            let path: Vec<_> = key.split('.').collect();
+            self.check_accessor(types, key.clone(), accessor_name(name, &path));
+        }
+    }
+
+    /// Check that the keys generate valid and unique variants of the key enum
+    ///
+    /// Keys with a method name error are skipped, as they would report the same problem.
+    fn check_variants(&mut self, keys: &BTreeMap<String, TranslationKey>) {
+        let reported: BTreeSet<_> = self.errors.iter().filter_map(ParseError::key).collect();
+        let mut variants: BTreeMap<String, &String> = BTreeMap::new();
+        let mut errors = Vec::new();
+
+        let keys = keys
+            .iter()
+            .filter(|(key, value)| {
+                !matches!(value, TranslationKey::Plural(_)) && !reported.contains(key.as_str())
+            })
+            .map(|(key, _)| key);
+        for key in keys {
+            let variant = variant_name(key);
+            if !is_identifier(&variant) {
+                errors.push(ParseError::InvalidKeyName {
+                    key: key.clone(),
+                    name: variant,
+                });
+            } else if let Some(other) = variants.get(&variant) {
+                errors.push(ParseError::KeyCollision {
+                    key: key.clone(),
+                    other: other.to_string(),
+                    name: variant,
+                });
+            } else {
+                variants.insert(variant, key);
+            }
+        }
+
+        self.errors.extend(errors);
+    }
+
+    fn check_tree(
+        &mut self,
+        tree: &KeyTree,
+        path: &[&str],
+        name: &Ident,
+        accessors: &mut BTreeMap<String, String>,
+    ) {
+        let full_key = |key: &str| {
+            let mut parts = path.to_vec();
+            parts.push(key);
+            parts.join(".")
+        };
+
+        let mut names = match path {
+            [] => self.methods.clone(),
+            _ => BTreeMap::new(),
+        };
+        for (key, _) in &tree.keys {
+            self.check(&mut names, &full_key(key), &snake_name(key));
+        }
+
+        for (group, child) in &tree.groups {
+            let mut child_path = path.to_vec();
+            child_path.push(group);
+
+            let key = full_key(group);
+            if self.check(&mut names, &key, &snake_name(group)) {
+                self.check_accessor(accessors, key, accessor_name(name, &child_path));
+            }
+
+            self.check_tree(child, &child_path, name, accessors);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, path::PathBuf};
+
+    use maplit::btreemap;
+    use tinyjson::JsonValue;
+
+    use super::{check_names, CodeGenerator};
+    use crate::{
+        builder::{KeyEscaping, LanguageId, ProviderConfig, RosettaConfig},
+        error::ParseError,
+        parser::TranslationData,
+        source::{SourceFile, SourceFormat},
+    };
+
+    fn source(path: &str) -> SourceFile {
+        SourceFile {
+            path: PathBuf::from(path),
+            format: SourceFormat::Json,
+        }
+    }
+
+    /// Parse translations and generate code, from scratch
+    fn generate(config: &RosettaConfig, flatten_keys: bool) -> String {
+        let en = r#"{
+            "hello": "Hello world!",
+            "display": "{name} is {age} years old, {city} {country}",
+            "auth": { "login": "Log in", "logout": "Log out", "title": "Auth" },
+            "settings": { "theme": "Theme", "language": "Language" },
+            "items": { "one": "{count} item in {cart}", "other": "{count} items in {cart}" }
+        }"#;
+        let others = btreemap! {
+            "fr" => r#"{ "hello": "Bonjour", "display": "{name} a {age} ans, {city} {country}" }"#,
+            "de" => r#"{ "hello": "Hallo", "auth": { "login": "Anmelden" } }"#,
+            "es" => r#"{ "items": { "one": "{count} artículo en {cart}", "other": "{count} artículos en {cart}" } }"#,
+        };
+
+        let mut parsed = TranslationData::from_fallback(en.parse::<JsonValue>().unwrap()).unwrap();
+        for (language, content) in others {
+            let content = content.parse::<JsonValue>().unwrap();
+            parsed
+                .parse_file(LanguageId(language.into()), content)
+                .unwrap();
+        }
+
+        let config = RosettaConfig {
+            flatten_keys,
+            ..config.clone()
+        };
+        CodeGenerator::new(&parsed, &config).generate().to_string()
+    }
+
+    fn config() -> RosettaConfig {
+        RosettaConfig {
+            fallback: (LanguageId("en".into()), source("en.json")),
+            others: btreemap! {
+                LanguageId("fr".into()) => source("fr.json"),
+                LanguageId("de".into()) => source("de.json"),
+                LanguageId("es".into()) => source("es.json"),
+            },
+            fallback_chains: BTreeMap::new(),
+            native_names: BTreeMap::new(),
+            name: "Lang".to_string(),
+            output: None,
+            flatten_keys: false,
+            diagnostics: false,
+            strict: false,
+            coverage_report: false,
+            key_escaping: KeyEscaping::default(),
+            key_enum: false,
+            runtime_lookup: false,
+            no_std: false,
+            provider: ProviderConfig::default(),
+        }
+    }
+
+    #[test]
+    fn generate_deterministic() {
+        let config = config();
+
+        for flatten_keys in [false, true] {
+            let expected = generate(&config, flatten_keys);
+            for _ in 0..10 {
+                assert_eq!(generate(&config, flatten_keys), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn generate_runtime_lookup() {
+        let mut config = config();
+        assert!(!generate(&config, false).contains("KEYS"));
+
+        config.runtime_lookup = true;
+        let code = generate(&config, false);
+        assert!(code.contains("pub fn get ("));
+        assert!(code.contains("pub fn get_formatted ("));
+    }
+
+    #[test]
+    fn generate_no_std() {
+        let config = RosettaConfig {
+            no_std: true,
+            runtime_lookup: true,
+            ..config()
+        };
+
+        for flatten_keys in [false, true] {
+            let code = generate(&config, flatten_keys);
+            for item in [":: std ::", "format !", ". to_string ()"] {
+                assert!(!code.contains(item), "`{}` found in generated code", item);
+            }
+        }
+    }
+
+    #[test]
+    fn check_key_names() {
+        let en = r#"{
+            "helloWorld": "Hello",
+            "hello_world": "Hello",
+            "type": "Type",
+            "1st": "First",
+            "get": "Get",
+            "auth": { "login": "Log in", "self": "Self" },
+            "auth_login": "Log in"
+        }"#;
+        let parsed = TranslationData::from_fallback(en.parse::<JsonValue>().unwrap()).unwrap();
+        let mut config = config();
+
+        let errors = check_names(&parsed, &config);
+        assert_eq!(errors.len(), 4);
+        assert!(errors.iter().all(|error| error.key() != Some("get")));
+
+        config.runtime_lookup = true;
+        let errors = check_names(&parsed, &config);
+        let expected = vec![
+            invalid("1st", "1_st"),
+            ParseError::KeyCollision {
+                key: "get".to_string(),
+                other: "Lang::get".to_string(),
+                name: "get".to_string(),
+            },
+            ParseError::KeyCollision {
+                key: "hello_world".to_string(),
+                other: "helloWorld".to_string(),
+                name: "hello_world".to_string(),
+            },
+            invalid("type", "type"),
+            invalid("auth.self", "self"),
+        ];
+        assert_eq!(errors, expected);
+
+        config.key_escaping = KeyEscaping::Raw;
+        let errors = check_names(&parsed, &config);
+        assert_eq!(errors.len(), 4);
+        assert!(!errors.contains(&invalid("type", "type")));
+
+        config.key_escaping = KeyEscaping::Prefix("key_".to_string());
+        config.flatten_keys = true;
+        let errors = check_names(&parsed, &config);
+        let expected = vec![
+            ParseError::KeyCollision {
+                key: "auth_login".to_string(),
+                other: "auth.login".to_string(),
+                name: "auth_login".to_string(),
+            },
+            ParseError::KeyCollision {
+                key: "get".to_string(),
+                other: "Lang::get".to_string(),
+                name: "get".to_string(),
+            },
+            ParseError::KeyCollision {
+                key: "hello_world".to_string(),
+                other: "helloWorld".to_string(),
+                name: "hello_world".to_string(),
+            },
+        ];
+        assert_eq!(errors, expected);
+    }
+
+    #[test]
+    fn check_key_enum_names() {
+        let en = r#"{
+            "auth": { "login": "Log in" },
+            "auth_login": "Log in",
+            "key": { "title": "Title" },
+            "translate": "Translate"
+        }"#;
+        let parsed = TranslationData::from_fallback(en.parse::<JsonValue>().unwrap()).unwrap();
+        let mut config = config();
+        assert!(check_names(&parsed, &config).is_empty());
+
+        config.key_enum = true;
+        let errors = check_names(&parsed, &config);
+        let expected = vec![
+            ParseError::KeyCollision {
+                key: "translate".to_string(),
+                other: "Lang::translate".to_string(),
+                name: "translate".to_string(),
+            },
+            ParseError::KeyCollision {
+                key: "key".to_string(),
+                other: "LangKey".to_string(),
+                name: "LangKey".to_string(),
+            },
+            ParseError::KeyCollision {
+                key: "auth_login".to_string(),
+                other: "auth.login".to_string(),
+                name: "AuthLogin".to_string(),
+            },
+        ];
+        assert_eq!(errors, expected);
+    }
+
+    #[test]
+    fn check_value_names() {
+        let en = r#"{
+            "auth": { "login": { "title": "Log in" } },
+            "auth_login": "Welcome {name}"
+        }"#;
+        let parsed = TranslationData::from_fallback(en.parse::<JsonValue>().unwrap()).unwrap();
+        let mut config = config();
+
+        let errors = check_names(&parsed, &config);
+        let expected = vec![ParseError::KeyCollision {
+            key: "auth_login".to_string(),
+            other: "auth.login".to_string(),
+            name: "LangAuthLogin".to_string(),
+        }];
+        assert_eq!(errors, expected);
+
+        config.flatten_keys = true;
+        assert!(check_names(&parsed, &config).is_empty());
+    }
+
+    fn invalid(key: &str, name: &str) -> ParseError {
+        ParseError::InvalidKeyName {
+            key: key.to_string(),
+            name: name.to_string(),
+        }
+    }
+}
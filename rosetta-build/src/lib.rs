@@ -18,7 +18,12 @@
 pub mod error;
 
 mod builder;
+mod coverage;
 mod gen;
 mod parser;
+mod source;
 
-pub use crate::builder::{config, RosettaBuilder};
+pub use crate::{
+    builder::{config, KeyEscaping, RosettaBuilder},
+    source::SourceFormat,
+};
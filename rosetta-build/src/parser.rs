@@ -2,11 +2,14 @@
 //!
 //! Files are parsed as [TranslationData] from a provided [JsonValue].
 //! Parsed keys are represented as [TranslationKey].
+//!
+//! Nested JSON objects are flattened, and their keys are stored as dot-separated
+//! paths (e.g. `auth.login.title`). Objects whose keys are all CLDR plural
+//! categories (`zero`, `one`, `two`, `few`, `many` and `other`) are parsed as plural keys.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{btree_map::Entry, BTreeMap, BTreeSet, HashMap};
 
-use lazy_static::lazy_static;
-use regex::Regex;
+use rosetta_i18n::provider::PluralCategory;
 use tinyjson::JsonValue;
 
 use crate::{builder::LanguageId, error::ParseError};
@@ -14,35 +17,59 @@ use crate::{builder::LanguageId, error::ParseError};
 /// Data structure containing all translation keys
 ///
 /// This struct should be initialized with the fallback language,
-/// then keys will be populated with other languages using the [`parse_file`] method.
+/// then keys will be populated with other languages using the [`parse_file_report`] method.
 ///
-/// [`parse_file`]: Self::parse_file
+/// [`parse_file_report`]: Self::parse_file_report
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct TranslationData {
     /// Parsed translation keys
-    pub(crate) keys: HashMap<String, TranslationKey>,
+    pub(crate) keys: BTreeMap<String, TranslationKey>,
 }
 
 impl TranslationData {
     /// Initialize a [`TranslationData`] instance from the fallback language
     pub(crate) fn from_fallback(file: JsonValue) -> Result<Self, ParseError> {
-        let parsed = ParsedFile::parse(file)?;
+        let (data, report) = Self::from_fallback_report(file);
+        report.into_result().map(|_| data)
+    }
+
+    /// Initialize a [`TranslationData`] instance from the fallback language,
+    /// collecting all the errors of the file
+    ///
+    /// Keys with errors are ignored.
+    pub(crate) fn from_fallback_report(file: JsonValue) -> (Self, FileReport) {
+        let mut report = FileReport::default();
+        let parsed = ParsedFile::parse(file, &mut report);
         let keys = parsed
             .keys
             .into_iter()
             .map(|(key, value)| (key, TranslationKey::from_parsed(value)))
             .collect();
 
-        Ok(Self { keys })
+        (Self { keys }, report)
     }
 
     /// Parse a language file and insert its content into the current [`TranslationData`]
+    #[cfg(test)]
     pub(crate) fn parse_file(
         &mut self,
         language: LanguageId,
         file: JsonValue,
     ) -> Result<(), ParseError> {
-        let parsed = ParsedFile::parse(file)?;
+        self.parse_file_report(language, file).into_result()
+    }
+
+    /// Parse a language file and insert its content into the current [`TranslationData`],
+    /// collecting all the errors and warnings of the file
+    ///
+    /// Keys with errors are ignored.
+    pub(crate) fn parse_file_report(
+        &mut self,
+        language: LanguageId,
+        file: JsonValue,
+    ) -> FileReport {
+        let mut report = FileReport::default();
+        let parsed = ParsedFile::parse(file, &mut report);
 
         for (key, parsed) in parsed.keys {
             match self.keys.get_mut(&key) {
@@ -52,16 +79,174 @@ impl TranslationData {
                         key: &key,
                         parsed,
                     };
-                    translation_key.insert_parsed(data)?
+                    if let Err(error) = translation_key.insert_parsed(data) {
+                        report.errors.push(error);
+                    }
                 }
-                None => println!(
-                    "cargo:warning=Key `{}` exists in {} but not in fallback language",
-                    key, language
-                ),
+                None => match self.nested_conflict(&key) {
+                    Some(error) => report.errors.push(error),
+                    None => report.unknown_keys.push(key),
+                },
             };
         }
 
-        Ok(())
+        report
+    }
+
+    /// Check that plural keys provide all the categories required by each language
+    ///
+    /// The `categories` function returns the plural categories required by a language.
+    /// The [`PluralCategory::Other`] category is always required.
+    pub(crate) fn check_plurals(
+        &self,
+        fallback: &LanguageId,
+        categories: impl Fn(&LanguageId) -> &'static [PluralCategory],
+    ) -> Vec<ParseError> {
+        let mut errors = Vec::new();
+
+        for (key, value) in &self.keys {
+            if let TranslationKey::Plural(inner) = value {
+                let values = std::iter::once((fallback, &inner.fallback)).chain(&inner.others);
+
+                for (language, values) in values {
+                    if let Err(error) = check_plural_categories(key, language, values, &categories)
+                    {
+                        errors.push(error);
+                    }
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Check that all the keys are translated in a language (strict mode)
+    ///
+    /// A key is missing if neither the language nor the languages of its fallback `chain`
+    /// provide a value, and untranslated if its value is identical to the fallback value.
+    pub(crate) fn check_translated(
+        &self,
+        language: &LanguageId,
+        chain: &[LanguageId],
+    ) -> Vec<ParseError> {
+        let mut errors = Vec::new();
+
+        for (key, value) in &self.keys {
+            let error = match value.matches_fallback(language) {
+                Some(false) => continue,
+                Some(true) => ParseError::UntranslatedValue {
+                    key: key.clone(),
+                    language: language.value().to_string(),
+                },
+                None if chain
+                    .iter()
+                    .any(|language| value.matches_fallback(language).is_some()) =>
+                {
+                    continue
+                }
+                None => ParseError::MissingTranslation {
+                    key: key.clone(),
+                    language: language.value().to_string(),
+                },
+            };
+
+            errors.push(error);
+        }
+
+        errors
+    }
+
+    /// Count the keys translated, identical to the fallback value and missing in a language
+    ///
+    /// Keys without value in the language are resolved with the languages of its fallback `chain`.
+    pub(crate) fn coverage(&self, language: &LanguageId, chain: &[LanguageId]) -> Coverage {
+        let mut coverage = Coverage::default();
+
+        for value in self.keys.values() {
+            let matches = std::iter::once(language)
+                .chain(chain)
+                .find_map(|language| value.matches_fallback(language));
+
+            match matches {
+                Some(false) => coverage.translated += 1,
+                Some(true) => coverage.identical += 1,
+                None => coverage.missing += 1,
+            }
+        }
+
+        coverage
+    }
+
+    /// Check if a key that doesn't exist in the fallback language conflicts with its nesting
+    ///
+    /// A conflict happens when a key is a string in a language but an object in the other.
+    fn nested_conflict(&self, key: &str) -> Option<ParseError> {
+        let prefix = format!("{}.", key);
+        if self.keys.keys().any(|other| other.starts_with(&prefix)) {
+            return Some(ParseError::InvalidType {
+                key: key.into(),
+                expected: "nested keys",
+            });
+        }
+
+        key.match_indices('.')
+            .map(|(index, _)| &key[..index])
+            .find_map(|parent| self.keys.get(parent).map(|value| (parent, value)))
+            .map(|(parent, value)| ParseError::InvalidType {
+                key: parent.into(),
+                expected: value.type_name(),
+            })
+    }
+}
+
+/// Errors and warnings found when parsing a translation file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct FileReport {
+    /// Errors found in the file
+    pub(crate) errors: Vec<ParseError>,
+    /// Keys that exist in the file but not in the fallback language
+    pub(crate) unknown_keys: Vec<String>,
+    /// Keys ignored because of an error
+    pub(crate) rejected_keys: BTreeSet<String>,
+}
+
+impl FileReport {
+    /// Add the error of a key rejected by the source file format
+    pub(crate) fn reject(&mut self, error: ParseError) {
+        if let Some(key) = error.key() {
+            self.rejected_keys.insert(key.to_string());
+        }
+        self.errors.push(error);
+    }
+
+    /// Returns the first error of the report, if any
+    pub(crate) fn into_result(self) -> Result<(), ParseError> {
+        match self.errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Translation coverage of a language
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Coverage {
+    /// Keys with a value different from the fallback value
+    pub(crate) translated: usize,
+    /// Keys with a value identical to the fallback value
+    pub(crate) identical: usize,
+    /// Keys without value
+    pub(crate) missing: usize,
+}
+
+impl Coverage {
+    /// Percentage of translated keys
+    pub(crate) fn percentage(&self) -> f64 {
+        let total = self.translated + self.identical + self.missing;
+        match total {
+            0 => 100.0,
+            total => self.translated as f64 * 100.0 / total as f64,
+        }
     }
 }
 
@@ -72,6 +257,7 @@ impl TranslationData {
 pub(crate) enum TranslationKey {
     Simple(SimpleKey),
     Formatted(FormattedKey),
+    Plural(PluralKey),
 }
 
 impl TranslationKey {
@@ -80,12 +266,27 @@ impl TranslationKey {
         match parsed {
             ParsedKey::Simple(value) => TranslationKey::Simple(SimpleKey {
                 fallback: value,
-                others: HashMap::new(),
+                others: BTreeMap::new(),
             }),
-            ParsedKey::Formatted { value, parameters } => TranslationKey::Formatted(FormattedKey {
+            ParsedKey::Formatted {
+                value,
+                parameters,
+                types,
+            } => TranslationKey::Formatted(FormattedKey {
                 fallback: value,
-                others: HashMap::new(),
+                others: BTreeMap::new(),
+                parameters,
+                types,
+            }),
+            ParsedKey::Plural {
+                values,
+                parameters,
+                types,
+            } => TranslationKey::Plural(PluralKey {
+                fallback: values,
+                others: BTreeMap::new(),
                 parameters,
+                types,
             }),
         }
     }
@@ -95,6 +296,37 @@ impl TranslationKey {
         match self {
             TranslationKey::Simple(inner) => inner.insert_parsed(data),
             TranslationKey::Formatted(inner) => inner.insert_parsed(data),
+            TranslationKey::Plural(inner) => inner.insert_parsed(data),
+        }
+    }
+
+    /// Check if the value of a language is identical to the fallback value
+    ///
+    /// Returns `None` if the language doesn't provide a value.
+    fn matches_fallback(&self, language: &LanguageId) -> Option<bool> {
+        match self {
+            TranslationKey::Simple(inner) => inner
+                .others
+                .get(language)
+                .map(|value| value == &inner.fallback),
+            TranslationKey::Formatted(inner) => inner
+                .others
+                .get(language)
+                .map(|value| value == &inner.fallback),
+            TranslationKey::Plural(inner) => inner.others.get(language).map(|values| {
+                values
+                    .iter()
+                    .all(|(category, value)| inner.fallback.get(category) == Some(value))
+            }),
+        }
+    }
+
+    /// Name of the key type, used in error messages
+    fn type_name(&self) -> &'static str {
+        match self {
+            TranslationKey::Simple(_) => "string",
+            TranslationKey::Formatted(_) => "formatted string",
+            TranslationKey::Plural(_) => "plural",
         }
     }
 }
@@ -105,7 +337,7 @@ pub(crate) struct SimpleKey {
     /// The key value for the fallback language
     pub(crate) fallback: String,
     /// Key values for other languages
-    pub(crate) others: HashMap<LanguageId, String>,
+    pub(crate) others: BTreeMap<LanguageId, String>,
 }
 
 impl SimpleKey {
@@ -131,16 +363,22 @@ pub(crate) struct FormattedKey {
     /// The key value for the fallback language
     pub(crate) fallback: String,
     /// Key values for other languages
-    pub(crate) others: HashMap<LanguageId, String>,
+    pub(crate) others: BTreeMap<LanguageId, String>,
     /// List of parameters in the value
-    pub(crate) parameters: HashSet<String>,
+    pub(crate) parameters: BTreeSet<String>,
+    /// Declared types of the parameters, in all languages
+    pub(crate) types: ParameterTypes,
 }
 
 impl FormattedKey {
     /// Inserts a new [`ParsedKey`] in this [`SimpleKey`]
     fn insert_parsed(&mut self, data: ParsedKeyData) -> Result<(), ParseError> {
-        let (value, parameters) = match data.parsed {
-            ParsedKey::Formatted { value, parameters } => (value, parameters),
+        let (value, parameters, types) = match data.parsed {
+            ParsedKey::Formatted {
+                value,
+                parameters,
+                types,
+            } => (value, parameters, types),
             _ => {
                 return Err(ParseError::InvalidType {
                     key: data.key.into(),
@@ -150,6 +388,7 @@ impl FormattedKey {
         };
 
         if parameters == self.parameters {
+            merge_types(data.key, &mut self.types, types)?;
             self.others.insert(data.language, value);
             Ok(())
         } else {
@@ -165,27 +404,170 @@ impl FormattedKey {
     }
 }
 
+/// Values of a plural key for each plural category
+pub(crate) type PluralValues = BTreeMap<PluralCategory, String>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Plural key, with a value for each plural category
+pub(crate) struct PluralKey {
+    /// The key values for the fallback language
+    pub(crate) fallback: PluralValues,
+    /// Key values for other languages
+    pub(crate) others: BTreeMap<LanguageId, PluralValues>,
+    /// List of parameters in the values, except `count`
+    pub(crate) parameters: BTreeSet<String>,
+    /// Declared types of the parameters, in all languages
+    pub(crate) types: ParameterTypes,
+}
+
+impl PluralKey {
+    /// Inserts a new [`ParsedKey`] in this [`PluralKey`]
+    fn insert_parsed(&mut self, data: ParsedKeyData) -> Result<(), ParseError> {
+        let (values, parameters, types) = match data.parsed {
+            ParsedKey::Plural {
+                values,
+                parameters,
+                types,
+            } => (values, parameters, types),
+            _ => {
+                return Err(ParseError::InvalidType {
+                    key: data.key.into(),
+                    expected: "plural",
+                })
+            }
+        };
+
+        if parameters == self.parameters {
+            merge_types(data.key, &mut self.types, types)?;
+            self.others.insert(data.language, values);
+            Ok(())
+        } else {
+            let missing: Vec<_> = self.parameters.difference(&parameters).cloned().collect();
+            let unknown: Vec<_> = parameters.difference(&self.parameters).cloned().collect();
+
+            Err(ParseError::InvalidParameters {
+                key: data.key.into(),
+                missing,
+                unknown,
+            })
+        }
+    }
+}
+
+/// Check that plural values provide all the categories required by a language
+fn check_plural_categories(
+    key: &str,
+    language: &LanguageId,
+    values: &PluralValues,
+    categories: impl Fn(&LanguageId) -> &'static [PluralCategory],
+) -> Result<(), ParseError> {
+    let missing: Vec<_> = categories(language)
+        .iter()
+        .filter(|category| **category != PluralCategory::Other)
+        .chain(Some(&PluralCategory::Other))
+        .filter(|category| !values.contains_key(category))
+        .map(|category| category_name(*category).to_string())
+        .collect();
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(ParseError::MissingPluralCategories {
+            key: key.into(),
+            language: language.value().into(),
+            missing,
+        })
+    }
+}
+
+/// Parse the name of a CLDR plural category
+pub(crate) fn parse_category(name: &str) -> Option<PluralCategory> {
+    match name {
+        "zero" => Some(PluralCategory::Zero),
+        "one" => Some(PluralCategory::One),
+        "two" => Some(PluralCategory::Two),
+        "few" => Some(PluralCategory::Few),
+        "many" => Some(PluralCategory::Many),
+        "other" => Some(PluralCategory::Other),
+        _ => None,
+    }
+}
+
+/// Name of a CLDR plural category
+pub(crate) fn category_name(category: PluralCategory) -> &'static str {
+    match category {
+        PluralCategory::Zero => "zero",
+        PluralCategory::One => "one",
+        PluralCategory::Two => "two",
+        PluralCategory::Few => "few",
+        PluralCategory::Many => "many",
+        PluralCategory::Other => "other",
+    }
+}
+
 /// Raw representation of a parsed file
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct ParsedFile {
-    keys: HashMap<String, ParsedKey>,
+    keys: BTreeMap<String, ParsedKey>,
 }
 
 impl ParsedFile {
     /// Parse a JSON [`JsonValue`] as a translations file
-    fn parse(file: JsonValue) -> Result<Self, ParseError> {
-        let input = match file {
-            JsonValue::Object(map) => map,
-            _ => return Err(ParseError::InvalidRoot),
+    ///
+    /// Keys with errors are ignored, and added to the `report` with their errors.
+    fn parse(file: JsonValue, report: &mut FileReport) -> Self {
+        let mut keys = BTreeMap::new();
+
+        match file {
+            JsonValue::Object(map) => Self::parse_object(None, map, &mut keys, report),
+            _ => report.errors.push(ParseError::InvalidRoot),
         };
 
-        let mut keys = HashMap::with_capacity(input.len());
-        for (key, value) in input {
-            let parsed = ParsedKey::parse(&key, value)?;
-            keys.insert(key, parsed);
-        }
+        ParsedFile { keys }
+    }
+
+    /// Parse a JSON object, recursively flattening nested objects
+    fn parse_object(
+        prefix: Option<&str>,
+        object: HashMap<String, JsonValue>,
+        keys: &mut BTreeMap<String, ParsedKey>,
+        report: &mut FileReport,
+    ) {
+        // Sort keys to report errors in a consistent order
+        let object: BTreeMap<_, _> = object.into_iter().collect();
+
+        for (key, value) in object {
+            let path = match prefix {
+                Some(prefix) => format!("{}.{}", prefix, key),
+                None => key,
+            };
+
+            let parsed = match value {
+                JsonValue::Object(nested) if ParsedKey::is_plural(&nested) => {
+                    ParsedKey::parse_plural(&path, nested)
+                }
+                JsonValue::Object(nested) => {
+                    Self::parse_object(Some(&path), nested, keys, report);
+                    continue;
+                }
+                value => ParsedKey::parse(&path, value),
+            };
 
-        Ok(ParsedFile { keys })
+            match parsed {
+                Ok(parsed) => match keys.entry(path) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(parsed);
+                    }
+                    Entry::Occupied(entry) => report.errors.push(ParseError::DuplicateKey {
+                        key: entry.key().clone(),
+                    }),
+                },
+                Err(error) => {
+                    report.errors.push(error);
+                    report.rejected_keys.insert(path);
+                }
+            }
+        }
     }
 }
 
@@ -201,7 +583,20 @@ enum ParsedKey {
         /// The raw key value
         value: String,
         /// List of parameters in the value
-        parameters: HashSet<String>,
+        parameters: BTreeSet<String>,
+        /// Declared types of the parameters
+        types: ParameterTypes,
+    },
+    /// Plural key with a value for each plural category
+    ///
+    /// Example : `{ "one": "{count} item", "other": "{count} items" }`
+    Plural {
+        /// The raw values for each category
+        values: PluralValues,
+        /// List of parameters in the values, except `count`
+        parameters: BTreeSet<String>,
+        /// Declared types of the parameters, except `count`
+        types: ParameterTypes,
     },
 }
 
@@ -209,30 +604,367 @@ impl ParsedKey {
     /// Parse a JSON [`Value`] as a key
     fn parse(key: &str, value: JsonValue) -> Result<Self, ParseError> {
         match value {
-            JsonValue::String(value) => Ok(Self::parse_string(value)),
+            JsonValue::String(value) => Self::parse_string(key, value),
             _ => Err(ParseError::InvalidValue { key: key.into() }),
         }
     }
 
-    fn parse_string(value: String) -> Self {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(r"\{([a-z_]+)\}").unwrap();
+    /// Check if a JSON object is a plural key (all keys are plural categories)
+    fn is_plural(object: &HashMap<String, JsonValue>) -> bool {
+        !object.is_empty() && object.keys().all(|key| parse_category(key).is_some())
+    }
+
+    /// Parse a JSON object as a plural key
+    fn parse_plural(key: &str, object: HashMap<String, JsonValue>) -> Result<Self, ParseError> {
+        let mut values = BTreeMap::new();
+        let mut parameters = BTreeSet::new();
+        let mut types = BTreeMap::new();
+
+        for (name, value) in object {
+            let value = match value {
+                JsonValue::String(value) => value,
+                _ => {
+                    return Err(ParseError::InvalidValue {
+                        key: format!("{}.{}", key, name),
+                    })
+                }
+            };
+
+            let (value, value_types) = parse_types(key, &value)?;
+            merge_types(key, &mut types, value_types)?;
+            parameters.extend(parse_parameters(&value));
+            if let Some(category) = parse_category(&name) {
+                values.insert(category, value);
+            }
         }
 
-        let matches: HashSet<_> = RE
-            .captures_iter(&value)
-            .map(|capture| capture[1].to_string())
-            .collect();
+        parameters.remove("count");
+        match types.remove("count") {
+            Some(ParameterType::U64) | None => (),
+            Some(found) => {
+                return Err(ParseError::ConflictingParameterTypes {
+                    key: key.into(),
+                    parameter: "count".into(),
+                    expected: ParameterType::U64.name(),
+                    found: found.name(),
+                })
+            }
+        }
+
+        Ok(Self::Plural {
+            values,
+            parameters,
+            types,
+        })
+    }
 
-        if matches.is_empty() {
-            Self::Simple(value)
+    fn parse_string(key: &str, value: String) -> Result<Self, ParseError> {
+        let (value, types) = parse_types(key, &value)?;
+        let parameters = parse_parameters(&value);
+
+        if parameters.is_empty() {
+            Ok(Self::Simple(value))
         } else {
-            Self::Formatted {
+            Ok(Self::Formatted {
                 value,
-                parameters: matches,
+                parameters,
+                types,
+            })
+        }
+    }
+}
+
+/// Declared types of parameters, by name
+pub(crate) type ParameterTypes = BTreeMap<String, ParameterType>;
+
+/// Type of a parameter, declared with a type annotation (e.g. `{count:u64}`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum ParameterType {
+    Str,
+    Bool,
+    Char,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+    F32,
+    F64,
+}
+
+impl ParameterType {
+    /// Parse a type annotation, `number` being an alias for `f64`
+    fn parse(name: &str) -> Option<Self> {
+        let parsed = match name {
+            "str" => Self::Str,
+            "bool" => Self::Bool,
+            "char" => Self::Char,
+            "i8" => Self::I8,
+            "i16" => Self::I16,
+            "i32" => Self::I32,
+            "i64" => Self::I64,
+            "i128" => Self::I128,
+            "isize" => Self::Isize,
+            "u8" => Self::U8,
+            "u16" => Self::U16,
+            "u32" => Self::U32,
+            "u64" => Self::U64,
+            "u128" => Self::U128,
+            "usize" => Self::Usize,
+            "f32" => Self::F32,
+            "f64" | "number" => Self::F64,
+            _ => return None,
+        };
+
+        Some(parsed)
+    }
+
+    /// Name of the type, which is also the name of the Rust primitive type
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Str => "str",
+            Self::Bool => "bool",
+            Self::Char => "char",
+            Self::I8 => "i8",
+            Self::I16 => "i16",
+            Self::I32 => "i32",
+            Self::I64 => "i64",
+            Self::I128 => "i128",
+            Self::Isize => "isize",
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::U128 => "u128",
+            Self::Usize => "usize",
+            Self::F32 => "f32",
+            Self::F64 => "f64",
+        }
+    }
+}
+
+/// Extract the type annotations of a string value
+///
+/// Returns the value without type annotations, that can be passed to `format!`.
+fn parse_types(key: &str, value: &str) -> Result<(String, ParameterTypes), ParseError> {
+    let segments = segments(value).map_err(|_| ParseError::UnescapedBrace { key: key.into() })?;
+    let mut output = String::with_capacity(value.len());
+    let mut types = BTreeMap::new();
+    let mut placeholders = BTreeMap::new();
+
+    for segment in segments {
+        match segment {
+            Segment::Text(text) => output.push_str(&escape(text)),
+            Segment::Parameter {
+                name: placeholder,
+                annotation,
+            } => {
+                let name = parameter_name(key, placeholder, annotation)?;
+                output.push_str(&format!("{{{}}}", name));
+
+                let other = *placeholders.entry(name.clone()).or_insert(placeholder);
+                if other != placeholder {
+                    return Err(ParseError::ConflictingPlaceholders {
+                        key: key.into(),
+                        placeholder: placeholder.into(),
+                        other: other.into(),
+                        parameter: name,
+                    });
+                }
+
+                if let Some(annotation) = annotation {
+                    let parsed = ParameterType::parse(annotation).ok_or_else(|| {
+                        ParseError::InvalidParameterType {
+                            key: key.into(),
+                            parameter: name.clone(),
+                            value: annotation.into(),
+                        }
+                    })?;
+
+                    let declared = BTreeMap::from([(name, parsed)]);
+                    merge_types(key, &mut types, declared)?;
+                }
             }
         }
     }
+
+    Ok((output, types))
+}
+
+/// Validate the name of a placeholder and convert it to the snake case name of the parameter
+///
+/// Names are converted word by word, keeping digits (`userName2` becomes `user_name2`).
+fn parameter_name(key: &str, name: &str, annotation: Option<&str>) -> Result<String, ParseError> {
+    let reason = if name.is_empty() {
+        "empty name"
+    } else if name.trim() != name || annotation.is_some_and(|value| value.trim() != value) {
+        "unexpected whitespace"
+    } else if name.starts_with(|c: char| c.is_ascii_digit())
+        || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        "not a valid identifier"
+    } else {
+        let chars: Vec<char> = name.chars().collect();
+        let mut output = String::with_capacity(name.len());
+        for (i, &char) in chars.iter().enumerate() {
+            if char.is_ascii_uppercase() && i > 0 {
+                let previous = chars[i - 1];
+                let next_lowercase = chars.get(i + 1).is_some_and(char::is_ascii_lowercase);
+                if previous.is_ascii_lowercase()
+                    || previous.is_ascii_digit()
+                    || (previous.is_ascii_uppercase() && next_lowercase)
+                {
+                    output.push('_');
+                }
+            }
+            output.push(char.to_ascii_lowercase());
+        }
+
+        if is_identifier(&output) {
+            return Ok(output);
+        }
+        "Rust keyword"
+    };
+
+    let placeholder = match annotation {
+        Some(annotation) => format!("{{{}:{}}}", name, annotation),
+        None => format!("{{{}}}", name),
+    };
+    Err(ParseError::InvalidPlaceholder {
+        key: key.into(),
+        placeholder,
+        reason,
+    })
+}
+
+/// Rust keywords, including reserved keywords
+pub(crate) const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Check if a name is a valid ASCII identifier that is not a keyword
+pub(crate) fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    let valid = match chars.next() {
+        Some(first) => {
+            (first.is_ascii_alphabetic() || first == '_')
+                && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+        }
+        None => false,
+    };
+
+    valid && name != "_" && !KEYWORDS.contains(&name)
+}
+
+/// Merge parameter types, returning an error if a parameter is declared with different types
+///
+/// Parameters without type annotation don't conflict with typed parameters.
+fn merge_types(
+    key: &str,
+    types: &mut ParameterTypes,
+    other: ParameterTypes,
+) -> Result<(), ParseError> {
+    for (parameter, found) in other {
+        match types.get(&parameter) {
+            Some(expected) if *expected != found => {
+                return Err(ParseError::ConflictingParameterTypes {
+                    key: key.into(),
+                    parameter,
+                    expected: expected.name(),
+                    found: found.name(),
+                })
+            }
+            _ => {
+                types.insert(parameter, found);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Part of a string value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Segment<'a> {
+    /// Literal text, escaped braces being unescaped
+    Text(&'a str),
+    /// Parameter, with its optional type annotation
+    Parameter {
+        name: &'a str,
+        annotation: Option<&'a str>,
+    },
+}
+
+/// Split a string value into text and parameters
+///
+/// Literal braces are escaped by doubling them (`{{` and `}}`), like with `format!`.
+/// Returns the byte offset of the first unbalanced brace. Parameter names are not validated.
+pub(crate) fn segments(value: &str) -> Result<Vec<Segment<'_>>, usize> {
+    let mut segments = Vec::new();
+    let mut rest = value;
+
+    while !rest.is_empty() {
+        let offset = value.len() - rest.len();
+
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            segments.push(Segment::Text(&rest[..1]));
+            rest = &rest[2..];
+        } else if rest.starts_with('{') {
+            let end = rest.find('}').ok_or(offset)?;
+            if rest[1..end].contains('{') {
+                return Err(offset);
+            }
+
+            let (name, annotation) = match rest[1..end].split_once(':') {
+                Some((name, annotation)) => (name, Some(annotation)),
+                None => (&rest[1..end], None),
+            };
+
+            segments.push(Segment::Parameter { name, annotation });
+            rest = &rest[end + 1..];
+        } else if rest.starts_with('}') {
+            return Err(offset);
+        } else {
+            let end = rest.find(['{', '}']).unwrap_or(rest.len());
+            segments.push(Segment::Text(&rest[..end]));
+            rest = &rest[end..];
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Extract the parameters of a string value
+pub(crate) fn parse_parameters(value: &str) -> BTreeSet<String> {
+    segments(value)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Parameter { name, .. } => Some(name.to_string()),
+            Segment::Text(_) => None,
+        })
+        .collect()
+}
+
+/// Escape the braces of a literal text
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('{', "{{").replace('}', "}}")
+}
+
+/// Unescape the braces of a string value without parameters
+pub(crate) fn unescape(value: &str) -> String {
+    value.replace("{{", "{").replace("}}", "}")
 }
 
 /// Data associated with a parsed key.
@@ -247,14 +979,17 @@ struct ParsedKeyData<'a> {
 
 #[cfg(test)]
 mod tests {
-    use super::{TranslationData, TranslationKey};
+    use super::{Coverage, ParameterType, TranslationData, TranslationKey};
     use crate::{
         builder::LanguageId,
         error::ParseError,
-        parser::{FormattedKey, SimpleKey},
+        parser::{FormattedKey, PluralKey, SimpleKey},
     };
 
-    use maplit::{hashmap, hashset};
+    use std::collections::BTreeMap;
+
+    use maplit::{btreemap, btreeset, hashmap};
+    use rosetta_i18n::provider::PluralCategory;
     use tinyjson::JsonValue;
 
     macro_rules! json {
@@ -272,11 +1007,11 @@ mod tests {
         parsed.parse_file(LanguageId("fr".into()), fr)?;
 
         assert_eq!(parsed.keys.len(), 1);
-        assert!(parsed.keys.get("hello").is_some());
+        assert!(parsed.keys.contains_key("hello"));
 
         let expected = TranslationKey::Simple(SimpleKey {
             fallback: "Hello world!".to_string(),
-            others: hashmap! {
+            others: btreemap! {
                 LanguageId("fr".into()) => "Bonjour le monde !".to_string()
             },
         });
@@ -295,14 +1030,15 @@ mod tests {
         parsed.parse_file(LanguageId("fr".into()), fr)?;
 
         assert_eq!(parsed.keys.len(), 1);
-        assert!(parsed.keys.get("hello").is_some());
+        assert!(parsed.keys.contains_key("hello"));
 
         let expected = TranslationKey::Formatted(FormattedKey {
             fallback: "Hello {name}!".to_string(),
-            others: hashmap! {
+            others: btreemap! {
                 LanguageId("fr".into()) => "Bonjour {name} !".to_string()
             },
-            parameters: hashset! { "name".to_string() },
+            parameters: btreeset! { "name".to_string() },
+            types: BTreeMap::new(),
         });
 
         assert_eq!(parsed.keys.get("hello").unwrap(), &expected);
@@ -310,6 +1046,211 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn parse_nested() -> Result<(), Box<dyn std::error::Error>> {
+        let en =
+            json!({ "auth": { "login": { "title": "Log in", "welcome": "Welcome {name}!" } } });
+        let fr = json!({ "auth": { "login": { "title": "Connexion" } } });
+
+        let mut parsed = TranslationData::from_fallback(en)?;
+        parsed.parse_file(LanguageId("fr".into()), fr)?;
+
+        assert_eq!(parsed.keys.len(), 2);
+
+        let expected = TranslationKey::Simple(SimpleKey {
+            fallback: "Log in".to_string(),
+            others: btreemap! {
+                LanguageId("fr".into()) => "Connexion".to_string()
+            },
+        });
+
+        assert_eq!(parsed.keys.get("auth.login.title").unwrap(), &expected);
+        assert!(parsed.keys.contains_key("auth.login.welcome"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_nested_duplicate() {
+        let en = json!({ "auth": { "title": "Log in" }, "auth.title": "Sign in" });
+        let result = TranslationData::from_fallback(en);
+        let expected = ParseError::DuplicateKey {
+            key: "auth.title".to_string(),
+        };
+        assert_eq!(result, Err(expected));
+    }
+
+    #[test]
+    fn parse_nested_invalid_type() {
+        let en = json!({ "auth": { "title": "Log in" } });
+        let fr = json!({ "auth": "Connexion" });
+
+        let mut parsed = TranslationData::from_fallback(en.clone()).unwrap();
+        let result = parsed.parse_file(LanguageId("fr".into()), fr);
+        let expected = ParseError::InvalidType {
+            key: "auth".to_string(),
+            expected: "nested keys",
+        };
+        assert_eq!(result, Err(expected));
+
+        let mut parsed = TranslationData::from_fallback(json!({ "auth": "Log in" })).unwrap();
+        let result = parsed.parse_file(LanguageId("fr".into()), en);
+        let expected = ParseError::InvalidType {
+            key: "auth".to_string(),
+            expected: "string",
+        };
+        assert_eq!(result, Err(expected));
+    }
+
+    #[test]
+    fn parse_nested_invalid_parameter() {
+        let en = json!({ "user": { "hello": "Hello {name}!" } });
+        let fr = json!({ "user": { "hello": "Bonjour {surname} !" } });
+
+        let mut parsed = TranslationData::from_fallback(en).unwrap();
+        let result = parsed.parse_file(LanguageId("fr".into()), fr);
+
+        let expected = ParseError::InvalidParameters {
+            key: "user.hello".to_string(),
+            missing: vec!["name".to_string()],
+            unknown: vec!["surname".to_string()],
+        };
+        assert_eq!(result, Err(expected));
+    }
+
+    #[test]
+    fn parse_plural() -> Result<(), Box<dyn std::error::Error>> {
+        let en =
+            json!({ "items": { "one": "One item in {cart}", "other": "{count} items in {cart}" } });
+        let fr = json!({ "items": { "one": "{count} article dans {cart}", "other": "{count} articles dans {cart}" } });
+
+        let mut parsed = TranslationData::from_fallback(en)?;
+        parsed.parse_file(LanguageId("fr".into()), fr)?;
+
+        let expected = TranslationKey::Plural(PluralKey {
+            fallback: btreemap! {
+                PluralCategory::One => "One item in {cart}".to_string(),
+                PluralCategory::Other => "{count} items in {cart}".to_string(),
+            },
+            others: btreemap! {
+                LanguageId("fr".into()) => btreemap! {
+                    PluralCategory::One => "{count} article dans {cart}".to_string(),
+                    PluralCategory::Other => "{count} articles dans {cart}".to_string(),
+                }
+            },
+            parameters: btreeset! { "cart".to_string() },
+            types: BTreeMap::new(),
+        });
+
+        assert_eq!(parsed.keys.get("items").unwrap(), &expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_plural_missing_category() {
+        let en = json!({ "items": { "one": "One item", "other": "{count} items" } });
+        let fr = json!({ "items": { "one": "{count} article" } });
+
+        let mut parsed = TranslationData::from_fallback(en).unwrap();
+        parsed.parse_file(LanguageId("fr".into()), fr).unwrap();
+
+        let fallback = LanguageId("en".into());
+        let result = parsed.check_plurals(&fallback, |_| &[PluralCategory::One]);
+        let expected = ParseError::MissingPluralCategories {
+            key: "items".to_string(),
+            language: "fr".to_string(),
+            missing: vec!["other".to_string()],
+        };
+        assert_eq!(result, vec![expected]);
+
+        let result = parsed.check_plurals(&fallback, |_| &[PluralCategory::Few]);
+        let expected = vec![
+            ParseError::MissingPluralCategories {
+                key: "items".to_string(),
+                language: "en".to_string(),
+                missing: vec!["few".to_string()],
+            },
+            ParseError::MissingPluralCategories {
+                key: "items".to_string(),
+                language: "fr".to_string(),
+                missing: vec!["few".to_string(), "other".to_string()],
+            },
+        ];
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn parse_report_collects_errors() {
+        let en = json!({ "hello": "Hello {", "name": "Hello {name}", "count": 42 });
+        let (parsed, report) = TranslationData::from_fallback_report(en);
+        assert_eq!(report.errors.len(), 2);
+        assert_eq!(parsed.keys.len(), 1);
+        let rejected: Vec<_> = report.rejected_keys.iter().map(String::as_str).collect();
+        assert_eq!(rejected, ["count", "hello"]);
+
+        let fr = json!({ "name": true, "unknown": "Inconnu" });
+        let mut parsed = parsed;
+        let report = parsed.parse_file_report(LanguageId("fr".into()), fr);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.unknown_keys, vec!["unknown".to_string()]);
+    }
+
+    #[test]
+    fn parse_check_translated() {
+        let en = json!({ "hello": "Hello", "ok": "OK", "name": "Hello {name}" });
+        let fr = json!({ "hello": "Bonjour", "ok": "OK" });
+        let fr_ca = json!({ "hello": "Allô" });
+
+        let mut parsed = TranslationData::from_fallback(en).unwrap();
+        parsed.parse_file(LanguageId("fr".into()), fr).unwrap();
+        parsed
+            .parse_file(LanguageId("fr-CA".into()), fr_ca)
+            .unwrap();
+
+        let result = parsed.check_translated(&LanguageId("fr".into()), &[]);
+        let expected = vec![
+            ParseError::MissingTranslation {
+                key: "name".to_string(),
+                language: "fr".to_string(),
+            },
+            ParseError::UntranslatedValue {
+                key: "ok".to_string(),
+                language: "fr".to_string(),
+            },
+        ];
+        assert_eq!(result, expected);
+
+        let coverage = Coverage {
+            translated: 1,
+            identical: 1,
+            missing: 1,
+        };
+        assert_eq!(parsed.coverage(&LanguageId("fr".into()), &[]), coverage);
+
+        let result =
+            parsed.check_translated(&LanguageId("fr-CA".into()), &[LanguageId("fr".into())]);
+        let expected = vec![ParseError::MissingTranslation {
+            key: "name".to_string(),
+            language: "fr-CA".to_string(),
+        }];
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn parse_plural_invalid_type() {
+        let en = json!({ "items": { "one": "One item", "other": "{count} items" } });
+        let fr = json!({ "items": "Articles" });
+
+        let mut parsed = TranslationData::from_fallback(en).unwrap();
+        let result = parsed.parse_file(LanguageId("fr".into()), fr);
+        let expected = ParseError::InvalidType {
+            key: "items".to_string(),
+            expected: "plural",
+        };
+        assert_eq!(result, Err(expected));
+    }
+
     #[test]
     fn parse_invalid_root() {
         let file = json!("invalid");
@@ -344,4 +1285,146 @@ mod tests {
         };
         assert_eq!(result, Err(expected));
     }
+
+    #[test]
+    fn parse_typed_parameters() -> Result<(), Box<dyn std::error::Error>> {
+        let en = json!({ "age": "{name:str} is {age:u64} years old." });
+        let fr = json!({ "age": "{name} a {age:u64} ans." });
+
+        let mut parsed = TranslationData::from_fallback(en)?;
+        parsed.parse_file(LanguageId("fr".into()), fr)?;
+
+        let expected = TranslationKey::Formatted(FormattedKey {
+            fallback: "{name} is {age} years old.".to_string(),
+            others: btreemap! {
+                LanguageId("fr".into()) => "{name} a {age} ans.".to_string()
+            },
+            parameters: btreeset! { "name".to_string(), "age".to_string() },
+            types: btreemap! {
+                "name".to_string() => ParameterType::Str,
+                "age".to_string() => ParameterType::U64,
+            },
+        });
+        assert_eq!(parsed.keys["age"], expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_conflicting_parameter_types() {
+        let en = json!({ "price": "Price: {price:f64}" });
+        let fr = json!({ "price": "Prix : {price:str}" });
+
+        let mut parsed = TranslationData::from_fallback(en).unwrap();
+        let result = parsed.parse_file(LanguageId("fr".into()), fr);
+
+        let expected = ParseError::ConflictingParameterTypes {
+            key: "price".to_string(),
+            parameter: "price".to_string(),
+            expected: "f64",
+            found: "str",
+        };
+        assert_eq!(result, Err(expected));
+
+        let en = json!({ "items": { "one": "{count} item", "other": "{count:str} items" } });
+        assert!(matches!(
+            TranslationData::from_fallback(en),
+            Err(ParseError::ConflictingParameterTypes { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_invalid_parameter_type() {
+        let en = json!({ "date": "Today is {date:datetime}" });
+
+        let expected = ParseError::InvalidParameterType {
+            key: "date".to_string(),
+            parameter: "date".to_string(),
+            value: "datetime".to_string(),
+        };
+        assert_eq!(TranslationData::from_fallback(en), Err(expected));
+    }
+
+    #[test]
+    fn parse_escaped_braces() -> Result<(), Box<dyn std::error::Error>> {
+        let en = json!({
+            "css": "a {{ color: red; }}",
+            "json": "{{\"name\": \"{name}\"}}",
+            "code": "fn {name}() {{}}"
+        });
+        let parsed = TranslationData::from_fallback(en)?;
+
+        match &parsed.keys["css"] {
+            TranslationKey::Simple(key) => assert_eq!(key.fallback, "a {{ color: red; }}"),
+            _ => panic!("expected a simple key"),
+        }
+        match &parsed.keys["json"] {
+            TranslationKey::Formatted(key) => {
+                assert_eq!(key.parameters, btreeset! { "name".to_string() })
+            }
+            _ => panic!("expected a formatted key"),
+        }
+        match &parsed.keys["code"] {
+            TranslationKey::Formatted(key) => assert_eq!(key.fallback, "fn {name}() {{}}"),
+            _ => panic!("expected a formatted key"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_unescaped_brace() {
+        for value in ["Hello {name!", "Hello {na{me}!", "}"] {
+            let en = JsonValue::Object(hashmap! { "key".to_string() => value.to_string().into() });
+            let expected = ParseError::UnescapedBrace {
+                key: "key".to_string(),
+            };
+            assert_eq!(
+                TranslationData::from_fallback(en),
+                Err(expected),
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn parse_placeholders() {
+        let en = json!({ "hello": "Hello {userName}, {count2} {HTTPCode:u16}" });
+        let parsed = TranslationData::from_fallback(en).unwrap();
+        match &parsed.keys["hello"] {
+            TranslationKey::Formatted(key) => {
+                assert_eq!(key.fallback, "Hello {user_name}, {count2} {http_code}")
+            }
+            _ => panic!("expected a formatted key"),
+        }
+
+        let invalid = [
+            ("{ name }", "unexpected whitespace"),
+            ("{name: u64}", "unexpected whitespace"),
+            ("{}", "empty name"),
+            ("{2nd}", "not a valid identifier"),
+            ("{user-name}", "not a valid identifier"),
+            ("{type}", "Rust keyword"),
+        ];
+        for (placeholder, reason) in invalid {
+            let value = format!("Hello {}", placeholder);
+            let en = JsonValue::Object(hashmap! { "key".to_string() => value.into() });
+            let expected = ParseError::InvalidPlaceholder {
+                key: "key".to_string(),
+                placeholder: placeholder.to_string(),
+                reason,
+            };
+            assert_eq!(TranslationData::from_fallback(en), Err(expected));
+        }
+
+        let en = json!({ "hello": "Hello {userName} ({user_name}, {userName})" });
+        let expected = ParseError::ConflictingPlaceholders {
+            key: "hello".to_string(),
+            placeholder: "user_name".to_string(),
+            other: "userName".to_string(),
+            parameter: "user_name".to_string(),
+        };
+        assert_eq!(TranslationData::from_fallback(en), Err(expected));
+    }
 }
@@ -0,0 +1,474 @@
+//! Fluent source files
+//!
+//! Fluent messages are converted to a [`JsonValue`] with the following rules:
+//! - messages are converted to string keys, and variables (`{ $name }`) to parameters
+//! - message and term references are inlined
+//! - a select expression on plural categories is converted to a plural key,
+//!   the selector variable being renamed to `count`
+//! - attributes of a message without value are converted to nested keys
+//!
+//! Other constructs (functions, parameterized terms, numeric variant keys...)
+//! cannot be compiled statically and are rejected with [`ParseError::UnsupportedSyntax`].
+//! Messages with an error are ignored, and the rest of the file is still converted.
+
+use std::{collections::HashMap, path::Path};
+
+use convert_case::{Case, Casing};
+use fluent_syntax::{
+    ast::{
+        Entry, Expression, InlineExpression, Message, Pattern, PatternElement, Term, VariantKey,
+    },
+    parser,
+    unicode::unescape_unicode_to_string,
+};
+use tinyjson::JsonValue;
+
+use crate::{
+    error::{BuildError, ParseError},
+    parser::{escape, parse_category},
+};
+
+/// Maximum depth of inlined message and term references
+const MAX_DEPTH: usize = 32;
+
+/// Parse the content of a Fluent file
+///
+/// Errors of messages that cannot be converted are added to `errors`.
+pub(super) fn parse(
+    path: &Path,
+    content: &str,
+    errors: &mut Vec<ParseError>,
+) -> Result<JsonValue, BuildError> {
+    let resource = match parser::parse(content) {
+        Ok(resource) => resource,
+        Err((_, mut errors)) => {
+            let error = errors.remove(0);
+            let line = content[..error.pos.start].matches('\n').count() + 1;
+
+            return Err(BuildError::SourceParse {
+                file: path.to_path_buf(),
+                line: Some(line),
+                message: error.to_string(),
+            });
+        }
+    };
+
+    let mut converter = Converter::default();
+    for entry in &resource.body {
+        match entry {
+            Entry::Message(message) => {
+                converter.messages.insert(message.id.name, message);
+            }
+            Entry::Term(term) => {
+                converter.terms.insert(term.id.name, term);
+            }
+            _ => (),
+        }
+    }
+
+    let mut object = HashMap::new();
+    for entry in &resource.body {
+        if let Entry::Message(message) = entry {
+            if let Some(value) = converter.message(message, errors) {
+                object.insert(message.id.name.to_string(), value);
+            }
+        }
+    }
+
+    Ok(JsonValue::Object(object))
+}
+
+/// Converter from Fluent messages to [`JsonValue`]
+#[derive(Default)]
+struct Converter<'a> {
+    messages: HashMap<&'a str, &'a Message<&'a str>>,
+    terms: HashMap<&'a str, &'a Term<&'a str>>,
+}
+
+impl<'a> Converter<'a> {
+    /// Convert a message to a string, plural or nested value
+    ///
+    /// Errors are added to `errors`, and returns `None` if the whole message is rejected.
+    fn message(&self, message: &Message<&str>, errors: &mut Vec<ParseError>) -> Option<JsonValue> {
+        let key = message.id.name;
+
+        let result = match (&message.value, message.attributes.is_empty()) {
+            (Some(value), true) => self.value(key, value),
+            (None, false) => {
+                let mut object = HashMap::new();
+                for attribute in &message.attributes {
+                    let name = attribute.id.name;
+                    match self.value(&format!("{}.{}", key, name), &attribute.value) {
+                        Ok(value) => {
+                            object.insert(name.to_string(), value);
+                        }
+                        Err(error) => errors.push(error),
+                    }
+                }
+
+                Ok(JsonValue::Object(object))
+            }
+            _ => Err(unsupported(key, "message with both a value and attributes")),
+        };
+
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                errors.push(error);
+                None
+            }
+        }
+    }
+
+    /// Convert a pattern to a string value, or to a plural value if it contains a select expression
+    fn value(&self, key: &str, pattern: &Pattern<&str>) -> Result<JsonValue, ParseError> {
+        let mut selects = pattern
+            .elements
+            .iter()
+            .enumerate()
+            .filter_map(|(i, element)| match element {
+                PatternElement::Placeable {
+                    expression: Expression::Select { selector, variants },
+                } => Some((i, selector, variants)),
+                _ => None,
+            });
+
+        let (position, selector, variants) = match (selects.next(), selects.next()) {
+            (None, _) => return Ok(JsonValue::String(self.pattern(key, pattern, None, 0)?)),
+            (Some(select), None) => select,
+            (Some(_), Some(_)) => return Err(unsupported(key, "multiple select expressions")),
+        };
+
+        let count = match selector {
+            InlineExpression::VariableReference { id } => id.name,
+            _ => {
+                return Err(unsupported(
+                    key,
+                    "select expression on a non-variable selector",
+                ))
+            }
+        };
+
+        let (before, after) = pattern.elements.split_at(position);
+        let before = self.elements(key, before, Some(count), 0)?;
+        let after = self.elements(key, &after[1..], Some(count), 0)?;
+
+        let mut values = HashMap::new();
+        let mut default = None;
+        for variant in variants {
+            let category = match &variant.key {
+                VariantKey::Identifier { name } if parse_category(name).is_some() => *name,
+                VariantKey::Identifier { .. } => {
+                    return Err(unsupported(
+                        key,
+                        "select expression on a non-plural category",
+                    ))
+                }
+                VariantKey::NumberLiteral { .. } => {
+                    return Err(unsupported(key, "numeric variant key"))
+                }
+            };
+
+            let value = format!(
+                "{}{}{}",
+                before,
+                self.pattern(key, &variant.value, Some(count), 0)?,
+                after
+            );
+
+            if variant.default {
+                default = Some(value.clone());
+            }
+            values.insert(category.to_string(), JsonValue::String(value));
+        }
+
+        if let Some(default) = default {
+            values
+                .entry("other".to_string())
+                .or_insert(JsonValue::String(default));
+        }
+
+        Ok(JsonValue::Object(values))
+    }
+
+    /// Convert a pattern without select expression to a string
+    fn pattern(
+        &self,
+        key: &str,
+        pattern: &Pattern<&str>,
+        count: Option<&str>,
+        depth: usize,
+    ) -> Result<String, ParseError> {
+        self.elements(key, &pattern.elements, count, depth)
+    }
+
+    fn elements(
+        &self,
+        key: &str,
+        elements: &[PatternElement<&str>],
+        count: Option<&str>,
+        depth: usize,
+    ) -> Result<String, ParseError> {
+        let mut output = String::new();
+
+        for element in elements {
+            match element {
+                PatternElement::TextElement { value } => output.push_str(&escape(value)),
+                PatternElement::Placeable { expression } => {
+                    self.expression(key, expression, count, depth, &mut output)?
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    fn expression(
+        &self,
+        key: &str,
+        expression: &Expression<&str>,
+        count: Option<&str>,
+        depth: usize,
+        output: &mut String,
+    ) -> Result<(), ParseError> {
+        match expression {
+            Expression::Inline(inline) => self.inline(key, inline, count, depth, output),
+            Expression::Select { .. } => Err(unsupported(key, "nested select expression")),
+        }
+    }
+
+    fn inline(
+        &self,
+        key: &str,
+        expression: &InlineExpression<&str>,
+        count: Option<&str>,
+        depth: usize,
+        output: &mut String,
+    ) -> Result<(), ParseError> {
+        match expression {
+            InlineExpression::StringLiteral { value } => {
+                output.push_str(&escape(&unescape_unicode_to_string(value)))
+            }
+            InlineExpression::NumberLiteral { value } => output.push_str(value),
+            InlineExpression::VariableReference { id } => {
+                let name = match count {
+                    Some(count) if count == id.name => "count".to_string(),
+                    _ => id.name.to_case(Case::Snake),
+                };
+                output.push('{');
+                output.push_str(&name);
+                output.push('}');
+            }
+            InlineExpression::MessageReference { id, attribute } => {
+                let message = self
+                    .messages
+                    .get(id.name)
+                    .ok_or_else(|| unsupported(key, "reference to an unknown message"))?;
+                let pattern = match attribute {
+                    Some(attribute) => message
+                        .attributes
+                        .iter()
+                        .find(|attr| attr.id.name == attribute.name)
+                        .map(|attr| &attr.value),
+                    None => message.value.as_ref(),
+                };
+                let pattern =
+                    pattern.ok_or_else(|| unsupported(key, "reference to an unknown message"))?;
+
+                self.reference(key, pattern, count, depth, output)?
+            }
+            InlineExpression::TermReference {
+                id,
+                attribute,
+                arguments,
+            } => {
+                if arguments.is_some() {
+                    return Err(unsupported(key, "parameterized term"));
+                }
+
+                let term = self
+                    .terms
+                    .get(id.name)
+                    .ok_or_else(|| unsupported(key, "reference to an unknown term"))?;
+                let pattern = match attribute {
+                    Some(attribute) => term
+                        .attributes
+                        .iter()
+                        .find(|attr| attr.id.name == attribute.name)
+                        .map(|attr| &attr.value)
+                        .ok_or_else(|| unsupported(key, "reference to an unknown term"))?,
+                    None => &term.value,
+                };
+
+                self.reference(key, pattern, count, depth, output)?
+            }
+            InlineExpression::FunctionReference { .. } => {
+                return Err(unsupported(key, "function call"))
+            }
+            InlineExpression::Placeable { expression } => {
+                self.expression(key, expression, count, depth, output)?
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Inline a referenced message or term
+    fn reference(
+        &self,
+        key: &str,
+        pattern: &Pattern<&str>,
+        count: Option<&str>,
+        depth: usize,
+        output: &mut String,
+    ) -> Result<(), ParseError> {
+        if depth >= MAX_DEPTH {
+            return Err(unsupported(key, "recursive reference"));
+        }
+
+        output.push_str(&self.pattern(key, pattern, count, depth + 1)?);
+        Ok(())
+    }
+}
+
+fn unsupported(key: &str, syntax: &'static str) -> ParseError {
+    ParseError::UnsupportedSyntax {
+        key: key.to_string(),
+        syntax,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::parse;
+    use crate::{
+        builder::LanguageId,
+        error::{BuildError, ParseError},
+        parser::{TranslationData, TranslationKey},
+    };
+
+    #[test]
+    fn fluent_messages() -> Result<(), Box<dyn std::error::Error>> {
+        let mut errors = Vec::new();
+        let en = parse(
+            Path::new("en.ftl"),
+            r#"
+-brand = Rosetta
+hello = Hello { "{" }world{ "}" }!
+hello-name = Hello { $userName }, welcome to { -brand }!
+login =
+    .title = Login
+    .welcome = Welcome back { $name }!
+"#,
+            &mut errors,
+        )?;
+        let fr = parse(
+            Path::new("fr.ftl"),
+            "hello = Bonjour le monde !",
+            &mut errors,
+        )?;
+
+        let mut parsed = TranslationData::from_fallback(en)?;
+        parsed.parse_file(LanguageId("fr".into()), fr)?;
+
+        assert_eq!(parsed.keys.len(), 4);
+        match &parsed.keys["hello"] {
+            TranslationKey::Simple(key) => assert_eq!(key.fallback, "Hello {{world}}!"),
+            _ => panic!("expected a simple key"),
+        }
+        assert!(parsed.keys.contains_key("login.title"));
+
+        match &parsed.keys["hello-name"] {
+            TranslationKey::Formatted(key) => {
+                assert_eq!(key.fallback, "Hello {user_name}, welcome to Rosetta!")
+            }
+            _ => panic!("expected a formatted key"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn fluent_plural() -> Result<(), Box<dyn std::error::Error>> {
+        let mut errors = Vec::new();
+        let en = parse(
+            Path::new("en.ftl"),
+            r#"
+items = You have { $num ->
+    [one] one item
+   *[other] { $num } items
+} in { $place }.
+"#,
+            &mut errors,
+        )?;
+
+        let parsed = TranslationData::from_fallback(en)?;
+        match &parsed.keys["items"] {
+            TranslationKey::Plural(key) => {
+                let other = &key.fallback[&rosetta_i18n::provider::PluralCategory::Other];
+                assert_eq!(other, "You have {count} items in {place}.");
+                assert!(key.parameters.contains("place"));
+            }
+            _ => panic!("expected a plural key"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn fluent_unsupported() -> Result<(), Box<dyn std::error::Error>> {
+        let mut errors = Vec::new();
+        let en = parse(
+            Path::new("en.ftl"),
+            r#"
+date = { DATETIME($date) }
+emails = { $unread ->
+    [0] No emails
+   *[other] Emails
+}
+hello = Hello world!
+login =
+    .title = Login
+    .welcome = Welcome { -unknown }!
+"#,
+            &mut errors,
+        )?;
+
+        let expected = [
+            ParseError::UnsupportedSyntax {
+                key: "date".to_string(),
+                syntax: "function call",
+            },
+            ParseError::UnsupportedSyntax {
+                key: "emails".to_string(),
+                syntax: "numeric variant key",
+            },
+            ParseError::UnsupportedSyntax {
+                key: "login.welcome".to_string(),
+                syntax: "reference to an unknown term",
+            },
+        ];
+        assert_eq!(errors, expected);
+
+        let parsed = TranslationData::from_fallback(en)?;
+        let keys: Vec<_> = parsed.keys.keys().map(String::as_str).collect();
+        assert_eq!(keys, ["hello", "login.title"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fluent_syntax_error() {
+        let result = parse(
+            Path::new("en.ftl"),
+            "hello = Hello\n\nworld",
+            &mut Vec::new(),
+        );
+        match result {
+            Err(BuildError::SourceParse { line, .. }) => assert_eq!(line, Some(3)),
+            _ => panic!("expected a Fluent parse error"),
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! JSON source files
+//!
+//! Files are parsed with [`tinyjson`], which doesn't keep track of positions.
+//! Positions of the keys are found with a separate scanner, only used to locate errors.
+
+use std::{collections::BTreeMap, iter::Peekable, path::Path, str::Chars};
+
+use tinyjson::JsonValue;
+
+use crate::error::BuildError;
+
+/// Parse the content of a JSON file
+pub(super) fn parse(path: &Path, content: &str) -> Result<JsonValue, BuildError> {
+    match content.parse::<JsonValue>() {
+        Ok(parsed) => Ok(parsed),
+        Err(error) => Err(BuildError::JsonParse {
+            file: path.to_path_buf(),
+            source: error,
+        }),
+    }
+}
+
+/// Find the position (line and column) of all the keys of a JSON file
+///
+/// Nested keys are flattened with dots, like in [`TranslationData`](crate::parser::TranslationData).
+/// Scanning stops at the first syntax error.
+pub(super) fn positions(content: &str) -> BTreeMap<String, (usize, usize)> {
+    let mut scanner = Scanner {
+        chars: content.chars().peekable(),
+        line: 1,
+        column: 1,
+        positions: BTreeMap::new(),
+    };
+    scanner.value(Some(""));
+
+    scanner.positions
+}
+
+struct Scanner<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+    positions: BTreeMap<String, (usize, usize)>,
+}
+
+impl Scanner<'_> {
+    fn next(&mut self) -> Option<char> {
+        let char = self.chars.next()?;
+        if char == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        Some(char)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next();
+        }
+    }
+
+    /// Scan a value, recording the keys of objects under `path` (ignored if `None`)
+    fn value(&mut self, path: Option<&str>) -> Option<()> {
+        self.skip_whitespace();
+        match self.peek()? {
+            '{' => self.object(path),
+            '[' => self.array(),
+            '"' => self.string().map(|_| ()),
+            _ => {
+                while self
+                    .peek()
+                    .is_some_and(|char| char.is_alphanumeric() || "+-.".contains(char))
+                {
+                    self.next();
+                }
+                Some(())
+            }
+        }
+    }
+
+    fn object(&mut self, path: Option<&str>) -> Option<()> {
+        self.next();
+        loop {
+            self.skip_whitespace();
+            match self.peek()? {
+                '}' => {
+                    self.next();
+                    return Some(());
+                }
+                ',' => {
+                    self.next();
+                }
+                _ => {
+                    let position = (self.line, self.column);
+                    let key = self.string()?;
+                    let path = path.map(|path| match path {
+                        "" => key,
+                        path => format!("{}.{}", path, key),
+                    });
+                    if let Some(path) = &path {
+                        self.positions.entry(path.clone()).or_insert(position);
+                    }
+
+                    self.skip_whitespace();
+                    if self.next()? != ':' {
+                        return None;
+                    }
+                    self.value(path.as_deref())?;
+                }
+            }
+        }
+    }
+
+    fn array(&mut self) -> Option<()> {
+        self.next();
+        loop {
+            self.skip_whitespace();
+            match self.peek()? {
+                ']' => {
+                    self.next();
+                    return Some(());
+                }
+                ',' => {
+                    self.next();
+                }
+                _ => self.value(None)?,
+            }
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        if self.next()? != '"' {
+            return None;
+        }
+
+        let mut output = String::new();
+        loop {
+            match self.next()? {
+                '"' => return Some(output),
+                '\\' => match self.next()? {
+                    'b' => output.push('\u{8}'),
+                    'f' => output.push('\u{c}'),
+                    'n' => output.push('\n'),
+                    'r' => output.push('\r'),
+                    't' => output.push('\t'),
+                    'u' => {
+                        let high = self.hex()?;
+                        let code = if (0xD800..0xDC00).contains(&high) {
+                            if self.next()? != '\\' || self.next()? != 'u' {
+                                return None;
+                            }
+                            let low = self.hex()?;
+                            0x10000 + ((high - 0xD800) << 10) + (low.checked_sub(0xDC00)?)
+                        } else {
+                            high
+                        };
+                        output.push(char::from_u32(code)?);
+                    }
+                    char => output.push(char),
+                },
+                char => output.push(char),
+            }
+        }
+    }
+
+    /// Read the four hexadecimal digits of an unicode escape
+    fn hex(&mut self) -> Option<u32> {
+        let mut code = 0;
+        for _ in 0..4 {
+            code = code * 16 + self.next()?.to_digit(16)?;
+        }
+
+        Some(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::positions;
+    use crate::{
+        error::{BuildError, Location, ParseError},
+        source::Locator,
+    };
+
+    #[test]
+    fn json_positions() {
+        let content = r#"{
+    "hello": "Hello world!",
+    "nested": {
+        "key": ["a", { "ignored": 1 }],
+        "escaped\"": true
+    },
+    "last": 42
+}"#;
+        let positions = positions(content);
+
+        assert_eq!(positions["hello"], (2, 5));
+        assert_eq!(positions["nested"], (3, 5));
+        assert_eq!(positions["nested.key"], (4, 9));
+        assert_eq!(positions["nested.escaped\""], (5, 9));
+        assert_eq!(positions["last"], (7, 5));
+        assert_eq!(positions.len(), 5);
+    }
+
+    #[test]
+    fn json_locate_error() {
+        let content = "{\n  \"hello\": \"Hello\",\n  \"items\": {\n    \"one\": 1\n  }\n}";
+        let locator = Locator {
+            path: PathBuf::from("locales/fr.json"),
+            positions: positions(content),
+            lines: content.lines().map(str::to_string).collect(),
+        };
+
+        let error = locator.locate(ParseError::InvalidValue {
+            key: "items.one".to_string(),
+        });
+        match &error {
+            BuildError::Located { location, .. } => assert_eq!(
+                location,
+                &Location {
+                    line: 4,
+                    column: 5,
+                    excerpt: "    \"one\": 1".to_string()
+                }
+            ),
+            _ => panic!("expected a located error"),
+        }
+        assert_eq!(
+            error.to_string(),
+            "failed to parse translations: `items.one` has an invalid type\n  \
+             --> locales/fr.json:4:5\n  |\n4 |     \"one\": 1\n  |     ^"
+        );
+
+        let error = locator.locate(ParseError::InvalidValue {
+            key: "items.two".to_string(),
+        });
+        assert!(matches!(error, BuildError::Located { location, .. } if location.line == 3));
+
+        let error = locator.locate(ParseError::InvalidRoot);
+        assert!(matches!(error, BuildError::Parse(ParseError::InvalidRoot)));
+    }
+
+    #[test]
+    fn json_locate_missing_line() {
+        let content = "{\n  \"hello\": 42\n}";
+        let locator = Locator {
+            path: PathBuf::from("locales/fr.json"),
+            positions: positions(content),
+            lines: Vec::new(),
+        };
+
+        let error = locator.locate(ParseError::InvalidValue {
+            key: "hello".to_string(),
+        });
+        assert!(matches!(
+            error,
+            BuildError::Parse(ParseError::InvalidValue { .. })
+        ));
+    }
+}
@@ -0,0 +1,245 @@
+//! Translation source files
+//!
+//! Source files are read as a [`JsonValue`] whatever their format, so that
+//! all formats share the same parsing and validation in [`parser`](crate::parser).
+
+#[cfg(feature = "fluent")]
+mod fluent;
+mod json;
+pub(crate) mod po;
+#[cfg(feature = "toml")]
+mod toml;
+#[cfg(feature = "xliff")]
+pub(crate) mod xliff;
+#[cfg(feature = "yaml")]
+mod yaml;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+
+use tinyjson::JsonValue;
+
+use crate::{
+    builder::{LanguageId, ProviderConfig},
+    error::{BuildError, Location, ParseError},
+};
+
+/// Format of a translation source file.
+///
+/// The format is detected from the file extension when using [`RosettaBuilder::source`],
+/// and can be explicitly defined with [`RosettaBuilder::source_with_format`].
+///
+/// [`RosettaBuilder::source`]: crate::RosettaBuilder::source
+/// [`RosettaBuilder::source_with_format`]: crate::RosettaBuilder::source_with_format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SourceFormat {
+    /// JSON file (default format)
+    Json,
+    /// YAML file (`.yaml` or `.yml`), requires the `yaml` feature
+    Yaml,
+    /// TOML file (`.toml`), requires the `toml` feature
+    Toml,
+    /// Fluent file (`.ftl`), requires the `fluent` feature
+    Fluent,
+    /// gettext PO file (`.po` or `.pot`)
+    Po,
+    /// XLIFF 2.0 file (`.xlf` or `.xliff`), requires the `xliff` feature
+    Xliff,
+}
+
+impl SourceFormat {
+    /// Detect the format of a file from its extension
+    ///
+    /// Files with an unknown extension are read as JSON.
+    pub(crate) fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("yaml") | Some("yml") => Self::Yaml,
+            Some("toml") => Self::Toml,
+            Some("ftl") => Self::Fluent,
+            Some("po") | Some("pot") => Self::Po,
+            Some("xlf") | Some("xliff") => Self::Xliff,
+            _ => Self::Json,
+        }
+    }
+
+    /// Name of the format, which is also the name of the cargo feature enabling it
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+            Self::Toml => "toml",
+            Self::Fluent => "fluent",
+            Self::Po => "po",
+            Self::Xliff => "xliff",
+        }
+    }
+
+    /// Check if the cargo feature required to read this format is enabled
+    pub(crate) fn is_enabled(self) -> bool {
+        match self {
+            Self::Json | Self::Po => true,
+            Self::Yaml => cfg!(feature = "yaml"),
+            Self::Toml => cfg!(feature = "toml"),
+            Self::Fluent => cfg!(feature = "fluent"),
+            Self::Xliff => cfg!(feature = "xliff"),
+        }
+    }
+}
+
+/// A translation source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SourceFile {
+    pub path: PathBuf,
+    pub format: SourceFormat,
+}
+
+impl SourceFile {
+    /// Open the file of `language` and read its content as a [`JsonValue`]
+    ///
+    /// The `provider` gives the plural rules of the language.
+    pub(crate) fn open(
+        &self,
+        language: &LanguageId,
+        provider: &ProviderConfig,
+    ) -> Result<SourceContent, BuildError> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(error) => {
+                return Err(BuildError::FileRead {
+                    file: self.path.clone(),
+                    source: error,
+                })
+            }
+        };
+
+        // Only Fluent files reject some keys while converting the file
+        #[cfg_attr(not(feature = "fluent"), allow(unused_mut))]
+        let mut errors = Vec::new();
+        let value = match self.format {
+            SourceFormat::Json => json::parse(&self.path, &content)?,
+            SourceFormat::Po => po::parse(
+                &self.path,
+                &content,
+                provider.categories(language),
+                |number| provider.plural(language, number),
+            )?,
+            #[cfg(feature = "yaml")]
+            SourceFormat::Yaml => yaml::parse(&self.path, &content)?,
+            #[cfg(feature = "toml")]
+            SourceFormat::Toml => self::toml::parse(&self.path, &content)?,
+            #[cfg(feature = "fluent")]
+            SourceFormat::Fluent => fluent::parse(&self.path, &content, &mut errors)?,
+            #[cfg(feature = "xliff")]
+            SourceFormat::Xliff => xliff::parse(&self.path, &content)?,
+            #[allow(unreachable_patterns)]
+            format => unreachable!("the {} format is disabled", format.name()),
+        };
+
+        Ok(SourceContent {
+            value,
+            errors,
+            locator: Locator::new(self, &content),
+        })
+    }
+}
+
+/// Content of a source file
+pub(crate) struct SourceContent {
+    /// Translations of the file
+    pub value: JsonValue,
+    /// Errors of the keys that could not be converted, which are missing from `value`
+    pub errors: Vec<ParseError>,
+    /// Positions of the keys of the file
+    pub locator: Locator,
+}
+
+/// Positions of the keys of a source file
+pub(crate) struct Locator {
+    path: PathBuf,
+    positions: BTreeMap<String, (usize, usize)>,
+    lines: Vec<String>,
+}
+
+impl Locator {
+    /// Read the positions of the keys of a file from its content
+    ///
+    /// Positions are only available for JSON and YAML files.
+    fn new(file: &SourceFile, content: &str) -> Self {
+        let positions = match file.format {
+            SourceFormat::Json => json::positions(content),
+            #[cfg(feature = "yaml")]
+            SourceFormat::Yaml => yaml::positions(content),
+            _ => BTreeMap::new(),
+        };
+
+        Self {
+            path: file.path.clone(),
+            positions,
+            lines: content.lines().map(str::to_string).collect(),
+        }
+    }
+
+    /// Convert a parse error to a [`BuildError`], with its position if the key is found
+    ///
+    /// Errors on a nested value (e.g. a plural category) are located at the closest parent key.
+    pub(crate) fn locate(&self, error: ParseError) -> BuildError {
+        let mut key = match error.key() {
+            Some(key) => key,
+            None => return BuildError::Parse(error),
+        };
+
+        loop {
+            if let Some(&(line, column)) = self.positions.get(key) {
+                let excerpt = match line.checked_sub(1).and_then(|index| self.lines.get(index)) {
+                    Some(excerpt) => excerpt.clone(),
+                    None => return BuildError::Parse(error),
+                };
+                let location = Location {
+                    line,
+                    column,
+                    excerpt,
+                };
+
+                return BuildError::Located {
+                    file: self.path.clone(),
+                    location,
+                    source: Box::new(error),
+                };
+            }
+
+            match key.rsplit_once('.') {
+                Some((parent, _)) => key = parent,
+                None => return BuildError::Parse(error),
+            }
+        }
+    }
+}
+
+/// Insert a value in nested objects, splitting the key on dots
+///
+/// Returns `false` if the key conflicts with another key.
+pub(super) fn insert_nested(
+    object: &mut HashMap<String, JsonValue>,
+    key: &str,
+    value: JsonValue,
+) -> bool {
+    match key.split_once('.') {
+        Some((name, rest)) => {
+            match object
+                .entry(name.to_string())
+                .or_insert_with(|| JsonValue::Object(HashMap::new()))
+            {
+                JsonValue::Object(nested) => insert_nested(nested, rest, value),
+                _ => false,
+            }
+        }
+        None if object.contains_key(key) => false,
+        None => {
+            object.insert(key.to_string(), value);
+            true
+        }
+    }
+}
@@ -0,0 +1,840 @@
+//! gettext PO source files
+//!
+//! Entries are identified by their `msgctxt` if provided, or by their `msgid`.
+//! Dots in the identifier are parsed as nested keys.
+//!
+//! Untranslated and fuzzy entries are ignored, as well as the header entry.
+//!
+//! ## Plural forms
+//! gettext and CLDR plural rules are different: the plural forms (`msgstr[n]`) are
+//! selected by the `plural` expression of the `Plural-Forms` header, whereas plural
+//! keys use the CLDR plural categories of the language provider. The expression is
+//! evaluated on sample numbers and compared with the categories returned by the provider,
+//! to map each plural form to a category. The English rules (`nplurals=2; plural=(n != 1);`)
+//! are used when the header is missing, like gettext does.
+//!
+//! Files whose plural forms don't map one-to-one to plural categories are rejected,
+//! for example when a language has more forms than categories in the provider.
+//! Categories that the provider never returns for an integer (such as `other` in
+//! Russian, which is only used for decimal numbers) take the value of the last form.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+    fmt::Write,
+    path::Path,
+};
+
+use rosetta_i18n::provider::PluralCategory;
+use tinyjson::JsonValue;
+
+use super::insert_nested;
+use crate::{
+    error::BuildError,
+    parser::{category_name, TranslationData, TranslationKey},
+};
+
+/// Numbers on which plural rules are compared
+const PLURAL_SAMPLES: [u64; 4] = [10_000, 100_000, 1_000_000, 1_000_000_000];
+
+/// Parse the content of a PO file
+///
+/// `categories` are the plural categories of the file language, and `plural`
+/// returns the plural category of a number in this language.
+pub(super) fn parse(
+    path: &Path,
+    content: &str,
+    categories: &[PluralCategory],
+    plural: impl Fn(u64) -> PluralCategory,
+) -> Result<JsonValue, BuildError> {
+    let error = |line, message| BuildError::PoParse {
+        file: path.to_path_buf(),
+        line,
+        message,
+    };
+
+    let entries = parse_entries(content).map_err(|(line, message)| error(line, message))?;
+    let header = entries
+        .iter()
+        .find(|entry| entry.context.is_none() && entry.id.is_empty());
+    let (forms, forms_line) = match header.and_then(|header| header.plural_forms()) {
+        Some((line, value)) => match PluralForms::parse(value) {
+            Some(forms) => (forms, Some(line)),
+            None => return Err(error(line, "invalid Plural-Forms header")),
+        },
+        None => (PluralForms::default(), None),
+    };
+
+    let mut mapping = None;
+    let mut root = HashMap::new();
+
+    for entry in entries {
+        if entry.fuzzy || (entry.context.is_none() && entry.id.is_empty()) {
+            continue;
+        }
+
+        let value = match entry.id_plural {
+            Some(_) => {
+                let mut strings = entry.strings;
+                strings.sort_by_key(|(index, _)| *index);
+
+                if strings.iter().any(|(_, value)| value.is_empty()) {
+                    continue;
+                }
+                if strings.len() != forms.count
+                    || strings
+                        .iter()
+                        .enumerate()
+                        .any(|(i, (index, _))| i != *index)
+                {
+                    return Err(error(
+                        entry.line,
+                        "plural forms don't match the Plural-Forms header",
+                    ));
+                }
+
+                if mapping.is_none() {
+                    let line = forms_line.unwrap_or(entry.line);
+                    let categories = forms
+                        .categories(categories, &plural)
+                        .map_err(|message| error(line, message))?;
+                    mapping = Some(categories);
+                }
+
+                let values = mapping
+                    .iter()
+                    .flatten()
+                    .map(|(category, form)| {
+                        (
+                            category_name(*category).to_string(),
+                            JsonValue::String(strings[*form].1.clone()),
+                        )
+                    })
+                    .collect();
+
+                JsonValue::Object(values)
+            }
+            None => match entry.strings.as_slice() {
+                [(0, value)] if value.is_empty() => continue,
+                [(0, value)] => JsonValue::String(value.clone()),
+                _ => return Err(error(entry.line, "expected a single msgstr")),
+            },
+        };
+
+        let key = entry.context.unwrap_or(entry.id);
+        if !insert_nested(&mut root, &key, value) {
+            return Err(error(entry.line, "duplicate or conflicting key"));
+        }
+    }
+
+    Ok(JsonValue::Object(root))
+}
+
+/// Plural forms of a PO file, read from the `Plural-Forms` header
+struct PluralForms {
+    /// Number of plural forms (`nplurals`)
+    count: usize,
+    /// Expression returning the plural form of a number (`plural`)
+    expression: Expression,
+}
+
+impl PluralForms {
+    /// Parse the value of the `Plural-Forms` header (e.g. `nplurals=2; plural=(n != 1);`)
+    fn parse(value: &str) -> Option<Self> {
+        let mut count = None;
+        let mut expression = None;
+
+        for field in value
+            .split(';')
+            .map(str::trim)
+            .filter(|field| !field.is_empty())
+        {
+            let (name, value) = field.split_once('=')?;
+            match name.trim() {
+                "nplurals" => count = Some(value.trim().parse().ok()?),
+                "plural" => expression = Some(Expression::parse(value)?),
+                _ => return None,
+            }
+        }
+
+        let count = count.filter(|count| *count > 0)?;
+        Some(Self {
+            count,
+            expression: expression?,
+        })
+    }
+
+    /// Map plural categories to plural forms
+    ///
+    /// Each form must be used for a single category, and each category must use a single form.
+    fn categories(
+        &self,
+        categories: &[PluralCategory],
+        plural: impl Fn(u64) -> PluralCategory,
+    ) -> Result<BTreeMap<PluralCategory, usize>, &'static str> {
+        const MISMATCH: &str =
+            "the Plural-Forms header doesn't match the plural categories of the language provider";
+
+        let mut forms = vec![None; self.count];
+        let mut mapping = BTreeMap::new();
+
+        for number in (0..=1000).chain(PLURAL_SAMPLES) {
+            let form = self
+                .expression
+                .eval(number)
+                .and_then(|form| usize::try_from(form).ok())
+                .filter(|form| *form < self.count)
+                .ok_or("the Plural-Forms header returns an invalid plural form")?;
+            let category = plural(number);
+
+            if *forms[form].get_or_insert(category) != category
+                || *mapping.entry(category).or_insert(form) != form
+            {
+                return Err(MISMATCH);
+            }
+        }
+
+        for category in categories {
+            mapping.entry(*category).or_insert(self.count - 1);
+        }
+
+        Ok(mapping)
+    }
+}
+
+impl Default for PluralForms {
+    /// English plural forms, used by gettext when the header is missing
+    fn default() -> Self {
+        Self {
+            count: 2,
+            expression: Expression::parse("n != 1").unwrap(),
+        }
+    }
+}
+
+/// Expression of the `Plural-Forms` header, a subset of C
+#[derive(Debug, PartialEq, Eq)]
+enum Expression {
+    Number(u64),
+    N,
+    Not(Box<Expression>),
+    Binary(Operator, Box<Expression>, Box<Expression>),
+    Conditional(Box<Expression>, Box<Expression>, Box<Expression>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Le,
+    Ge,
+    Lt,
+    Gt,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+/// Binary operators with their precedence, two-character operators first
+const OPERATORS: [(&str, Operator, usize); 13] = [
+    ("||", Operator::Or, 0),
+    ("&&", Operator::And, 1),
+    ("==", Operator::Eq, 2),
+    ("!=", Operator::Ne, 2),
+    ("<=", Operator::Le, 3),
+    (">=", Operator::Ge, 3),
+    ("<", Operator::Lt, 3),
+    (">", Operator::Gt, 3),
+    ("+", Operator::Add, 4),
+    ("-", Operator::Sub, 4),
+    ("*", Operator::Mul, 5),
+    ("/", Operator::Div, 5),
+    ("%", Operator::Rem, 5),
+];
+
+impl Expression {
+    fn parse(value: &str) -> Option<Self> {
+        let mut parser = ExpressionParser { rest: value };
+        let expression = parser.conditional()?;
+        parser.rest.trim().is_empty().then_some(expression)
+    }
+
+    /// Evaluate the expression, returning `None` on a division by zero
+    fn eval(&self, n: u64) -> Option<u64> {
+        Some(match self {
+            Expression::Number(value) => *value,
+            Expression::N => n,
+            Expression::Not(inner) => (inner.eval(n)? == 0) as u64,
+            Expression::Conditional(condition, then, otherwise) => match condition.eval(n)? {
+                0 => otherwise.eval(n)?,
+                _ => then.eval(n)?,
+            },
+            Expression::Binary(operator, left, right) => {
+                let (left, right) = (left.eval(n)?, right.eval(n)?);
+                match operator {
+                    Operator::Or => (left != 0 || right != 0) as u64,
+                    Operator::And => (left != 0 && right != 0) as u64,
+                    Operator::Eq => (left == right) as u64,
+                    Operator::Ne => (left != right) as u64,
+                    Operator::Le => (left <= right) as u64,
+                    Operator::Ge => (left >= right) as u64,
+                    Operator::Lt => (left < right) as u64,
+                    Operator::Gt => (left > right) as u64,
+                    Operator::Add => left.wrapping_add(right),
+                    Operator::Sub => left.wrapping_sub(right),
+                    Operator::Mul => left.wrapping_mul(right),
+                    Operator::Div => left.checked_div(right)?,
+                    Operator::Rem => left.checked_rem(right)?,
+                }
+            }
+        })
+    }
+}
+
+/// Recursive descent parser of [`Expression`]
+struct ExpressionParser<'a> {
+    rest: &'a str,
+}
+
+impl ExpressionParser<'_> {
+    /// Consume `token` if it is the next token
+    fn eat(&mut self, token: &str) -> bool {
+        match self.rest.trim_start().strip_prefix(token) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn conditional(&mut self) -> Option<Expression> {
+        let condition = self.binary(0)?;
+        if !self.eat("?") {
+            return Some(condition);
+        }
+
+        let then = self.conditional()?;
+        if !self.eat(":") {
+            return None;
+        }
+        let otherwise = self.conditional()?;
+
+        Some(Expression::Conditional(
+            Box::new(condition),
+            Box::new(then),
+            Box::new(otherwise),
+        ))
+    }
+
+    /// Parse binary operators with a precedence of at least `precedence`
+    fn binary(&mut self, precedence: usize) -> Option<Expression> {
+        if precedence > 5 {
+            return self.unary();
+        }
+
+        let mut left = self.binary(precedence + 1)?;
+        'outer: loop {
+            for (token, operator, level) in OPERATORS {
+                if level == precedence && self.eat(token) {
+                    let right = self.binary(precedence + 1)?;
+                    left = Expression::Binary(operator, Box::new(left), Box::new(right));
+                    continue 'outer;
+                }
+            }
+
+            return Some(left);
+        }
+    }
+
+    fn unary(&mut self) -> Option<Expression> {
+        if self.eat("!") {
+            return Some(Expression::Not(Box::new(self.unary()?)));
+        }
+        if self.eat("(") {
+            let inner = self.conditional()?;
+            return self.eat(")").then_some(inner);
+        }
+        if self.eat("n") {
+            return Some(Expression::N);
+        }
+
+        self.rest = self.rest.trim_start();
+        let end = self
+            .rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest.len());
+        let value = self.rest[..end].parse().ok()?;
+        self.rest = &self.rest[end..];
+
+        Some(Expression::Number(value))
+    }
+}
+
+/// Entry of a PO file
+#[derive(Debug, Default)]
+struct Entry {
+    line: usize,
+    fuzzy: bool,
+    context: Option<String>,
+    id: String,
+    id_plural: Option<String>,
+    strings: Vec<(usize, String)>,
+}
+
+impl Entry {
+    /// Line and value of the `Plural-Forms` field of the header entry
+    fn plural_forms(&self) -> Option<(usize, &str)> {
+        let (_, header) = self.strings.first()?;
+        header.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.trim()
+                .eq_ignore_ascii_case("plural-forms")
+                .then(|| (self.line, value.trim()))
+        })
+    }
+}
+
+/// Field of an [`Entry`] that is being read
+#[derive(Debug, Clone, Copy)]
+enum Field {
+    Context,
+    Id,
+    IdPlural,
+    Str,
+}
+
+/// Parse the entries of a PO file
+///
+/// Errors are returned with the line where they occurred.
+fn parse_entries(content: &str) -> Result<Vec<Entry>, (usize, &'static str)> {
+    let mut entries = Vec::new();
+    let mut entry = Entry::default();
+    let mut has_id = false;
+    let mut field = None;
+
+    for (i, line) in content.lines().enumerate() {
+        let number = i + 1;
+        let line = line.trim();
+
+        // An entry ends with an empty line, or when a new entry starts after a msgstr
+        let starts_entry = line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with("msgctxt")
+            || (line.starts_with("msgid") && !line.starts_with("msgid_plural"));
+        if starts_entry && !entry.strings.is_empty() {
+            entries.push(std::mem::take(&mut entry));
+            has_id = false;
+            field = None;
+        }
+
+        if line.is_empty() {
+            continue;
+        } else if let Some(flags) = line.strip_prefix("#,") {
+            entry.fuzzy |= flags.split(',').any(|flag| flag.trim() == "fuzzy");
+            continue;
+        } else if line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('"') {
+            let value = parse_string(line).ok_or((number, "invalid string"))?;
+            match field {
+                Some(Field::Context) => entry
+                    .context
+                    .get_or_insert_with(String::new)
+                    .push_str(&value),
+                Some(Field::Id) => entry.id.push_str(&value),
+                Some(Field::IdPlural) => entry
+                    .id_plural
+                    .get_or_insert_with(String::new)
+                    .push_str(&value),
+                Some(Field::Str) => entry.strings.last_mut().unwrap().1.push_str(&value),
+                None => return Err((number, "unexpected string")),
+            }
+            continue;
+        }
+
+        let (keyword, rest) = line
+            .split_once(|c: char| c.is_whitespace())
+            .ok_or((number, "expected a keyword followed by a string"))?;
+        let value = parse_string(rest.trim()).ok_or((number, "invalid string"))?;
+
+        if entry.line == 0 {
+            entry.line = number;
+        }
+
+        field = Some(match keyword {
+            "msgctxt" => {
+                entry.context = Some(value);
+                Field::Context
+            }
+            "msgid" if has_id => return Err((number, "missing msgstr")),
+            "msgid" => {
+                entry.id = value;
+                has_id = true;
+                Field::Id
+            }
+            "msgid_plural" if has_id => {
+                entry.id_plural = Some(value);
+                Field::IdPlural
+            }
+            "msgstr" if has_id => {
+                entry.strings.push((0, value));
+                Field::Str
+            }
+            keyword if has_id && keyword.starts_with("msgstr[") && keyword.ends_with(']') => {
+                let index = keyword["msgstr[".len()..keyword.len() - 1]
+                    .parse()
+                    .map_err(|_| (number, "invalid plural form index"))?;
+                entry.strings.push((index, value));
+                Field::Str
+            }
+            "msgid_plural" | "msgstr" => return Err((number, "missing msgid")),
+            _ if keyword.starts_with("msgstr[") => return Err((number, "missing msgid")),
+            _ => return Err((number, "unknown keyword")),
+        });
+    }
+
+    if has_id && entry.strings.is_empty() {
+        return Err((entry.line, "missing msgstr"));
+    } else if !entry.strings.is_empty() {
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+/// Parse a quoted PO string
+fn parse_string(value: &str) -> Option<String> {
+    let value = value.strip_prefix('"')?.strip_suffix('"')?;
+    let mut output = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => output.push(match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                'r' => '\r',
+                '"' => '"',
+                '\\' => '\\',
+                _ => return None,
+            }),
+            '"' => return None,
+            c => output.push(c),
+        }
+    }
+
+    Some(output)
+}
+
+/// Escape a string to be written in a PO file
+fn escape(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('"');
+
+    for c in value.chars() {
+        match c {
+            '\n' => output.push_str("\\n"),
+            '\t' => output.push_str("\\t"),
+            '\r' => output.push_str("\\r"),
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            c => output.push(c),
+        }
+    }
+
+    output.push('"');
+    output
+}
+
+/// Generate a POT template from the keys of the fallback language
+///
+/// Keys are used as `msgctxt` and the fallback values as `msgid`, and
+/// the parameters of each key are listed in a translator comment.
+pub(crate) fn template(data: &TranslationData) -> String {
+    let mut output =
+        String::from("msgid \"\"\nmsgstr \"\"\n\"Content-Type: text/plain; charset=UTF-8\\n\"\n");
+
+    for (key, value) in &data.keys {
+        output.push('\n');
+
+        let mut parameters: Vec<&str> = match value {
+            TranslationKey::Simple(_) => Vec::new(),
+            TranslationKey::Formatted(inner) => {
+                inner.parameters.iter().map(String::as_str).collect()
+            }
+            TranslationKey::Plural(inner) => inner
+                .parameters
+                .iter()
+                .map(String::as_str)
+                .chain(Some("count"))
+                .collect(),
+        };
+        parameters.sort_unstable();
+
+        if !parameters.is_empty() {
+            writeln!(output, "#. Parameters: {}", parameters.join(", ")).unwrap();
+        }
+
+        writeln!(output, "msgctxt {}", escape(key)).unwrap();
+
+        match value {
+            TranslationKey::Simple(inner) => {
+                writeln!(output, "msgid {}\nmsgstr \"\"", escape(&inner.fallback)).unwrap()
+            }
+            TranslationKey::Formatted(inner) => {
+                writeln!(output, "msgid {}\nmsgstr \"\"", escape(&inner.fallback)).unwrap()
+            }
+            TranslationKey::Plural(inner) => {
+                let other = &inner.fallback[&PluralCategory::Other];
+                let one = inner.fallback.get(&PluralCategory::One).unwrap_or(other);
+
+                writeln!(
+                    output,
+                    "msgid {}\nmsgid_plural {}\nmsgstr[0] \"\"\nmsgstr[1] \"\"",
+                    escape(one),
+                    escape(other)
+                )
+                .unwrap()
+            }
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use rosetta_i18n::provider::PluralCategory;
+
+    use super::{parse, template};
+    use crate::{
+        builder::LanguageId,
+        error::BuildError,
+        parser::{TranslationData, TranslationKey},
+    };
+
+    const CATEGORIES: &[PluralCategory] = &[PluralCategory::One, PluralCategory::Other];
+
+    fn english(number: u64) -> PluralCategory {
+        match number {
+            1 => PluralCategory::One,
+            _ => PluralCategory::Other,
+        }
+    }
+
+    #[test]
+    fn po_entries() -> Result<(), Box<dyn std::error::Error>> {
+        let en = parse(
+            Path::new("en.po"),
+            r#"
+msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+
+msgid "hello"
+msgstr "Hello world!"
+
+#. Parameters: name
+msgctxt "auth.login.welcome"
+msgid "Welcome back {name}!"
+msgstr ""
+"Welcome back "
+"{name}!"
+
+#, fuzzy
+msgid "fuzzy"
+msgstr "Fuzzy"
+
+msgctxt "items"
+msgid "{count} item"
+msgid_plural "{count} items"
+msgstr[0] "{count} item"
+msgstr[1] "{count} items"
+"#,
+            CATEGORIES,
+            english,
+        )?;
+        let fr = parse(
+            Path::new("fr.po"),
+            "msgid \"hello\"\nmsgstr \"Bonjour le monde !\"\n\nmsgctxt \"items\"\nmsgid \"{count} item\"\nmsgid_plural \"{count} items\"\nmsgstr[0] \"\"\nmsgstr[1] \"\"",
+            CATEGORIES,
+            english,
+        )?;
+
+        let mut parsed = TranslationData::from_fallback(en)?;
+        parsed.parse_file(LanguageId("fr".into()), fr)?;
+
+        assert_eq!(parsed.keys.len(), 3);
+        assert!(matches!(parsed.keys["hello"], TranslationKey::Simple(_)));
+        assert!(matches!(
+            parsed.keys["auth.login.welcome"],
+            TranslationKey::Formatted(_)
+        ));
+        match &parsed.keys["items"] {
+            TranslationKey::Plural(key) => {
+                assert_eq!(key.fallback[&PluralCategory::One], "{count} item");
+                assert!(key.others.is_empty());
+            }
+            _ => panic!("expected a plural key"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn po_invalid_plural_forms() {
+        let result = parse(
+            Path::new("en.po"),
+            "msgid \"item\"\nmsgid_plural \"items\"\nmsgstr[0] \"a\"\nmsgstr[1] \"b\"\nmsgstr[2] \"c\"",
+            CATEGORIES,
+            english,
+        );
+        match result {
+            Err(BuildError::PoParse { line, .. }) => assert_eq!(line, 1),
+            _ => panic!("expected a PO parse error"),
+        }
+    }
+
+    #[test]
+    fn po_plural_forms() -> Result<(), Box<dyn std::error::Error>> {
+        let categories = &[
+            PluralCategory::One,
+            PluralCategory::Few,
+            PluralCategory::Many,
+            PluralCategory::Other,
+        ];
+        let russian = |number: u64| match (number % 10, number % 100) {
+            (1, n) if n != 11 => PluralCategory::One,
+            (2..=4, n) if !(12..=14).contains(&n) => PluralCategory::Few,
+            _ => PluralCategory::Many,
+        };
+
+        let ru = parse(
+            Path::new("ru.po"),
+            r#"
+msgid ""
+msgstr ""
+"Plural-Forms: nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2);\n"
+
+msgctxt "items"
+msgid "{count} item"
+msgid_plural "{count} items"
+msgstr[0] "{count} предмет"
+msgstr[1] "{count} предмета"
+msgstr[2] "{count} предметов"
+"#,
+            categories,
+            russian,
+        )?;
+
+        let parsed = TranslationData::from_fallback(ru)?;
+        match &parsed.keys["items"] {
+            TranslationKey::Plural(key) => {
+                assert_eq!(key.fallback[&PluralCategory::One], "{count} предмет");
+                assert_eq!(key.fallback[&PluralCategory::Few], "{count} предмета");
+                assert_eq!(key.fallback[&PluralCategory::Many], "{count} предметов");
+                assert_eq!(key.fallback[&PluralCategory::Other], "{count} предметов");
+            }
+            _ => panic!("expected a plural key"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn po_plural_forms_mismatch() {
+        let french = |number: u64| match number {
+            0 | 1 => PluralCategory::One,
+            _ => PluralCategory::Other,
+        };
+        let entry = "msgid \"item\"\nmsgid_plural \"items\"\nmsgstr[0] \"a\"\nmsgstr[1] \"b\"";
+
+        // English plural forms are used without header
+        let result = parse(Path::new("fr.po"), entry, CATEGORIES, french);
+        match result {
+            Err(BuildError::PoParse { line, message, .. }) => {
+                assert_eq!(line, 1);
+                assert!(message.contains("doesn't match the plural categories"));
+            }
+            _ => panic!("expected a PO parse error"),
+        }
+
+        let header = "msgid \"\"\nmsgstr \"Plural-Forms: nplurals=2; plural=(n > 1);\\n\"\n\n";
+        let result = parse(
+            Path::new("fr.po"),
+            &format!("{}{}", header, entry),
+            CATEGORIES,
+            french,
+        );
+        assert!(result.is_ok());
+
+        let header = "msgid \"\"\nmsgstr \"Plural-Forms: nplurals=2; plural=n >;\\n\"\n\n";
+        let result = parse(
+            Path::new("fr.po"),
+            &format!("{}{}", header, entry),
+            CATEGORIES,
+            french,
+        );
+        assert!(matches!(result, Err(BuildError::PoParse { line: 1, .. })));
+    }
+
+    #[test]
+    fn po_syntax_error() {
+        let result = parse(
+            Path::new("en.po"),
+            "msgid \"hello\"\nmsgstr \"Hello\"\n\nmsgid \"world",
+            CATEGORIES,
+            english,
+        );
+        match result {
+            Err(BuildError::PoParse { line, .. }) => assert_eq!(line, 4),
+            _ => panic!("expected a PO parse error"),
+        }
+    }
+
+    #[test]
+    fn po_template() -> Result<(), Box<dyn std::error::Error>> {
+        let en = tinyjson::JsonValue::Object(maplit::hashmap! {
+            "hello".to_string() => "Hello \"world\"!".to_string().into(),
+            "hello_name".to_string() => "Hello {name}!".to_string().into(),
+            "items".to_string() => tinyjson::JsonValue::Object(maplit::hashmap! {
+                "one".to_string() => "{count} {kind}".to_string().into(),
+                "other".to_string() => "{count} {kind}s".to_string().into(),
+            }),
+        });
+        let parsed = TranslationData::from_fallback(en)?;
+
+        let expected = r#"msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+
+msgctxt "hello"
+msgid "Hello \"world\"!"
+msgstr ""
+
+#. Parameters: name
+msgctxt "hello_name"
+msgid "Hello {name}!"
+msgstr ""
+
+#. Parameters: count, kind
+msgctxt "items"
+msgid "{count} {kind}"
+msgid_plural "{count} {kind}s"
+msgstr[0] ""
+msgstr[1] ""
+"#;
+        assert_eq!(template(&parsed), expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+//! TOML source files
+//!
+//! TOML tables are converted to a [`JsonValue`], so nested tables are parsed
+//! as nested keys or plural keys like JSON objects.
+
+use std::path::Path;
+
+use tinyjson::JsonValue;
+use toml::{Table, Value};
+
+use crate::error::BuildError;
+
+/// Parse the content of a TOML file
+pub(super) fn parse(path: &Path, content: &str) -> Result<JsonValue, BuildError> {
+    match toml::from_str::<Table>(content) {
+        Ok(table) => Ok(convert(Value::Table(table))),
+        Err(error) => {
+            let line = error
+                .span()
+                .map(|span| content[..span.start].matches('\n').count() + 1);
+
+            Err(BuildError::SourceParse {
+                file: path.to_path_buf(),
+                line,
+                message: error.message().to_string(),
+            })
+        }
+    }
+}
+
+/// Convert a TOML [`Value`] to a [`JsonValue`]
+fn convert(value: Value) -> JsonValue {
+    match value {
+        Value::String(value) => JsonValue::String(value),
+        Value::Integer(value) => JsonValue::Number(value as f64),
+        Value::Float(value) => JsonValue::Number(value),
+        Value::Boolean(value) => JsonValue::Boolean(value),
+        Value::Datetime(_) => JsonValue::Null,
+        Value::Array(values) => JsonValue::Array(values.into_iter().map(convert).collect()),
+        Value::Table(table) => JsonValue::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, convert(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::parse;
+    use crate::{
+        builder::LanguageId,
+        error::{BuildError, ParseError},
+        parser::TranslationData,
+    };
+
+    #[test]
+    fn toml_nested_plural() -> Result<(), Box<dyn std::error::Error>> {
+        let en = parse(
+            Path::new("en.toml"),
+            r#"
+hello = "Hello world!"
+
+[auth.login]
+welcome = "Welcome back {name}!"
+
+[items]
+one = "{count} item"
+other = "{count} items"
+"#,
+        )?;
+        let fr = parse(Path::new("fr.toml"), r#"hello = "Bonjour le monde !""#)?;
+
+        let mut parsed = TranslationData::from_fallback(en)?;
+        parsed.parse_file(LanguageId("fr".into()), fr)?;
+
+        assert_eq!(parsed.keys.len(), 3);
+        assert!(parsed.keys.contains_key("auth.login.welcome"));
+        assert!(parsed.keys.contains_key("items"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn toml_invalid_value() {
+        let file = parse(Path::new("en.toml"), "count = 42").unwrap();
+        let expected = ParseError::InvalidValue {
+            key: "count".to_string(),
+        };
+        assert_eq!(TranslationData::from_fallback(file), Err(expected));
+    }
+
+    #[test]
+    fn toml_syntax_error() {
+        let result = parse(Path::new("en.toml"), "hello = \"Hello\"\nworld = ");
+        match result {
+            Err(BuildError::SourceParse { line, .. }) => assert_eq!(line, Some(2)),
+            _ => panic!("expected a TOML parse error"),
+        }
+    }
+}
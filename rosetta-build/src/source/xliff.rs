@@ -0,0 +1,423 @@
+//! XLIFF 2.0 source files
+//!
+//! Each unit is identified by its `id` (dots are parsed as nested keys) and
+//! the content of its `<target>` elements is read as the translated value.
+//! Plural keys are exported as one unit per plural category (`items.one`, `items.other`).
+//!
+//! Parameters are encoded as `<ph>` elements with the parameter in their `equiv` attribute,
+//! so that CAT tools protect them. Placeholders of the target without `equiv` attribute
+//! are resolved using the placeholder of the source with the same `id`.
+
+use std::{collections::HashMap, fmt::Write, path::Path};
+
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+use rosetta_i18n::provider::PluralCategory;
+use tinyjson::JsonValue;
+
+use super::insert_nested;
+use crate::{
+    builder::LanguageId,
+    error::BuildError,
+    parser::{self, category_name, segments, Segment, TranslationData, TranslationKey},
+};
+
+/// Plural categories in the CLDR order
+const PLURAL_CATEGORIES: [PluralCategory; 6] = [
+    PluralCategory::Zero,
+    PluralCategory::One,
+    PluralCategory::Two,
+    PluralCategory::Few,
+    PluralCategory::Many,
+    PluralCategory::Other,
+];
+
+/// Parse the content of a XLIFF 2.0 file
+pub(super) fn parse(path: &Path, content: &str) -> Result<JsonValue, BuildError> {
+    let mut reader = Reader::from_str(content);
+    let error = |position: u64, message: String| {
+        let line = content.as_bytes()[..position as usize]
+            .iter()
+            .filter(|byte| **byte == b'\n')
+            .count()
+            + 1;
+
+        BuildError::SourceParse {
+            file: path.to_path_buf(),
+            line: Some(line),
+            message,
+        }
+    };
+
+    let mut root = HashMap::new();
+    let mut unit: Option<Unit> = None;
+    let mut element = None;
+
+    loop {
+        let position = reader.buffer_position();
+        let event = reader
+            .read_event()
+            .map_err(|err| error(position, err.to_string()))?;
+
+        let empty = matches!(event, Event::Empty(_));
+        match event {
+            Event::Start(start) | Event::Empty(start) => {
+                let name = start.local_name();
+                match (name.as_ref(), &mut unit) {
+                    (b"unit", _) => {
+                        let id = attribute(&start, "id")
+                            .map_err(|err| error(position, err))?
+                            .ok_or_else(|| error(position, "unit without id".into()))?;
+
+                        unit = Some(Unit {
+                            id,
+                            position,
+                            ..Default::default()
+                        });
+                    }
+                    (b"source", Some(_)) => element = Some(Element::Source),
+                    (b"target", Some(unit)) => {
+                        element = Some(Element::Target);
+                        unit.has_target = true;
+                    }
+                    (b"ph", Some(unit)) => {
+                        let id = attribute(&start, "id").map_err(|err| error(position, err))?;
+                        let value =
+                            match attribute(&start, "equiv").map_err(|err| error(position, err))? {
+                                Some(value) => Some(value),
+                                None => {
+                                    attribute(&start, "disp").map_err(|err| error(position, err))?
+                                }
+                            };
+
+                        match element {
+                            Some(Element::Source) => {
+                                if let (Some(id), Some(value)) = (id, value) {
+                                    unit.placeholders.insert(id, value);
+                                }
+                            }
+                            Some(Element::Target) => {
+                                let value = value
+                                    .or_else(|| {
+                                        id.and_then(|id| unit.placeholders.get(&id).cloned())
+                                    })
+                                    .ok_or_else(|| error(position, "unknown placeholder".into()))?;
+                                unit.target.push_str(&value);
+                            }
+                            None => (),
+                        }
+                    }
+                    _ => (),
+                }
+
+                if empty && matches!(name.as_ref(), b"source" | b"target") {
+                    element = None;
+                }
+            }
+            Event::End(end) => match end.local_name().as_ref() {
+                b"unit" => {
+                    if let Some(unit) = unit.take() {
+                        if unit.has_target
+                            && !unit.target.is_empty()
+                            && !insert_nested(&mut root, &unit.id, JsonValue::String(unit.target))
+                        {
+                            return Err(error(
+                                unit.position,
+                                "duplicate or conflicting unit id".into(),
+                            ));
+                        }
+                    }
+                }
+                b"source" | b"target" => element = None,
+                _ => (),
+            },
+            Event::Text(text) => {
+                if let (Some(Element::Target), Some(unit)) = (element, &mut unit) {
+                    let text = text
+                        .unescape()
+                        .map_err(|err| error(position, err.to_string()))?;
+                    unit.target.push_str(&parser::escape(&text));
+                }
+            }
+            Event::CData(data) => {
+                if let (Some(Element::Target), Some(unit)) = (element, &mut unit) {
+                    let text = std::str::from_utf8(&data)
+                        .map_err(|err| error(position, err.to_string()))?;
+                    unit.target.push_str(&parser::escape(text));
+                }
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+
+    Ok(JsonValue::Object(root))
+}
+
+/// Unit of a XLIFF file that is being read
+#[derive(Debug, Default)]
+struct Unit {
+    id: String,
+    position: u64,
+    /// Placeholders of the source, by id
+    placeholders: HashMap<String, String>,
+    target: String,
+    has_target: bool,
+}
+
+/// Element of a segment that is being read
+#[derive(Debug, Clone, Copy)]
+enum Element {
+    Source,
+    Target,
+}
+
+/// Read the value of an attribute
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>, String> {
+    match element.try_get_attribute(name) {
+        Ok(Some(attribute)) => match attribute.unescape_value() {
+            Ok(value) => Ok(Some(value.into_owned())),
+            Err(error) => Err(error.to_string()),
+        },
+        Ok(None) => Ok(None),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+/// Generate a XLIFF 2.0 file to translate the keys of the fallback language
+///
+/// `categories` are the plural categories of the target language.
+pub(crate) fn export(
+    data: &TranslationData,
+    name: &str,
+    fallback: &LanguageId,
+    target: &LanguageId,
+    categories: &[PluralCategory],
+) -> String {
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(
+        output,
+        "<xliff xmlns=\"urn:oasis:names:tc:xliff:document:2.0\" version=\"2.0\" srcLang=\"{}\" trgLang=\"{}\">",
+        escape(fallback.value()),
+        escape(target.value())
+    )
+    .unwrap();
+    writeln!(output, "  <file id=\"{}\">", escape(name)).unwrap();
+
+    for (key, value) in &data.keys {
+        match value {
+            TranslationKey::Simple(inner) => {
+                unit(&mut output, key, &inner.fallback, inner.others.get(target))
+            }
+            TranslationKey::Formatted(inner) => {
+                unit(&mut output, key, &inner.fallback, inner.others.get(target))
+            }
+            TranslationKey::Plural(inner) => {
+                let values = inner.others.get(target);
+                let other = &inner.fallback[&PluralCategory::Other];
+
+                for category in PLURAL_CATEGORIES.iter().filter(|category| {
+                    categories.contains(category)
+                        || **category == PluralCategory::Other
+                        || values.is_some_and(|values| values.contains_key(category))
+                }) {
+                    let id = format!("{}.{}", key, category_name(*category));
+                    let source = inner.fallback.get(category).unwrap_or(other);
+                    let target = values.and_then(|values| values.get(category));
+
+                    unit(&mut output, &id, source, target);
+                }
+            }
+        }
+    }
+
+    output.push_str("  </file>\n</xliff>\n");
+    output
+}
+
+/// Write a translation unit
+fn unit(output: &mut String, id: &str, source: &str, target: Option<&String>) {
+    writeln!(output, "    <unit id=\"{}\">\n      <segment>", escape(id)).unwrap();
+    writeln!(output, "        <source>{}</source>", content(source)).unwrap();
+    if let Some(target) = target {
+        writeln!(output, "        <target>{}</target>", content(target)).unwrap();
+    }
+    output.push_str("      </segment>\n    </unit>\n");
+}
+
+/// Encode a value as segment content, with parameters as `<ph>` elements
+///
+/// Placeholder ids are derived from the parameter name, so that they match
+/// between the source and the target.
+fn content(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut occurrences: HashMap<&str, usize> = HashMap::new();
+
+    for segment in segments(value).unwrap_or_default() {
+        let name = match segment {
+            Segment::Text(text) => {
+                output.push_str(&escape(text));
+                continue;
+            }
+            Segment::Parameter { name, .. } => name,
+        };
+
+        let occurrence = occurrences.entry(name).or_insert(0);
+        let id = match *occurrence {
+            0 => name.to_string(),
+            n => format!("{}-{}", name, n),
+        };
+        *occurrence += 1;
+
+        write!(
+            output,
+            "<ph id=\"{}\" disp=\"{{{}}}\" equiv=\"{{{}}}\"/>",
+            id, name, name
+        )
+        .unwrap();
+    }
+
+    output
+}
+
+/// Escape XML special characters
+fn escape(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            c => output.push(c),
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use maplit::hashmap;
+    use rosetta_i18n::provider::PluralCategory;
+    use tinyjson::JsonValue;
+
+    use super::{export, parse};
+    use crate::{
+        builder::LanguageId,
+        error::{BuildError, ParseError},
+        parser::TranslationData,
+    };
+
+    const CATEGORIES: &[PluralCategory] = &[PluralCategory::One, PluralCategory::Other];
+
+    fn json(value: &str) -> JsonValue {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn xliff_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let en = json(
+            r#"{
+                "hello": "Hello {{world}}!",
+                "hello_name": "Hello {name} & {name}!",
+                "items": { "one": "{count} item", "other": "{count} items" }
+            }"#,
+        );
+        let fr = json(
+            r#"{
+                "hello": "Bonjour {{monde}} !",
+                "hello_name": "Bonjour {name} et {name} !",
+                "items": { "one": "{count} élément", "other": "{count} éléments" }
+            }"#,
+        );
+        let (en_id, fr_id) = (LanguageId("en".into()), LanguageId("fr".into()));
+
+        let mut parsed = TranslationData::from_fallback(en.clone())?;
+        parsed.parse_file(fr_id.clone(), fr)?;
+
+        let exported = export(&parsed, "Lang", &en_id, &fr_id, CATEGORIES);
+        assert!(exported.contains("<target>Bonjour {monde} !</target>"));
+        assert!(exported.contains(
+            r#"<source>Hello <ph id="name" disp="{name}" equiv="{name}"/> &amp; <ph id="name-1" disp="{name}" equiv="{name}"/>!</source>"#
+        ));
+
+        let mut imported = TranslationData::from_fallback(en)?;
+        imported.parse_file(fr_id, parse(Path::new("fr.xlf"), &exported)?)?;
+        assert_eq!(imported, parsed);
+
+        Ok(())
+    }
+
+    #[test]
+    fn xliff_placeholders() -> Result<(), Box<dyn std::error::Error>> {
+        let fr = parse(
+            Path::new("fr.xlf"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<xliff xmlns="urn:oasis:names:tc:xliff:document:2.0" version="2.0" srcLang="en" trgLang="fr">
+  <file id="Lang">
+    <unit id="auth.welcome">
+      <segment>
+        <source>Welcome <ph id="name" equiv="{name}"/>!</source>
+        <target>Bienvenue <ph id="name"/> !</target>
+      </segment>
+    </unit>
+    <unit id="hello">
+      <segment>
+        <source>Hello world!</source>
+      </segment>
+    </unit>
+  </file>
+</xliff>"#,
+        )?;
+
+        let expected = hashmap! {
+            "auth".to_string() => JsonValue::Object(hashmap! {
+                "welcome".to_string() => JsonValue::String("Bienvenue {name} !".into())
+            })
+        };
+        assert_eq!(fr, JsonValue::Object(expected));
+
+        Ok(())
+    }
+
+    #[test]
+    fn xliff_invalid_parameters() -> Result<(), Box<dyn std::error::Error>> {
+        let en = json(r#"{ "hello_name": "Hello {name}!" }"#);
+        let fr = parse(
+            Path::new("fr.xlf"),
+            r#"<xliff version="2.0"><file id="f"><unit id="hello_name"><segment>
+                <source>Hello <ph id="name" equiv="{name}"/>!</source>
+                <target>Bonjour <ph id="name" equiv="{nom}"/> !</target>
+            </segment></unit></file></xliff>"#,
+        )?;
+
+        let mut parsed = TranslationData::from_fallback(en)?;
+        let result = parsed.parse_file(LanguageId("fr".into()), fr);
+        let expected = ParseError::InvalidParameters {
+            key: "hello_name".to_string(),
+            missing: vec!["name".to_string()],
+            unknown: vec!["nom".to_string()],
+        };
+        assert_eq!(result, Err(expected));
+
+        Ok(())
+    }
+
+    #[test]
+    fn xliff_syntax_error() {
+        let result = parse(
+            Path::new("fr.xlf"),
+            "<xliff>\n<file id=\"f\">\n</unit>\n</xliff>",
+        );
+        match result {
+            Err(BuildError::SourceParse { line, .. }) => assert_eq!(line, Some(3)),
+            _ => panic!("expected a XLIFF parse error"),
+        }
+    }
+}
@@ -0,0 +1,276 @@
+//! YAML source files
+//!
+//! YAML documents are converted to a [`JsonValue`]. Mapping keys that are not
+//! strings (e.g. numbers or booleans) are converted to strings, and other values
+//! are kept as-is so they are rejected by the parser like JSON values.
+//!
+//! Positions of the keys are read from the events of the YAML parser, only used to locate errors.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+use tinyjson::JsonValue;
+use yaml_rust2::{
+    parser::{Event, MarkedEventReceiver, Parser},
+    scanner::Marker,
+    Yaml, YamlLoader,
+};
+
+use crate::error::BuildError;
+
+/// Parse the content of a YAML file
+pub(super) fn parse(path: &Path, content: &str) -> Result<JsonValue, BuildError> {
+    let documents =
+        YamlLoader::load_from_str(content).map_err(|error| BuildError::SourceParse {
+            file: path.to_path_buf(),
+            line: Some(error.marker().line()),
+            message: error.info().to_string(),
+        })?;
+
+    Ok(documents
+        .into_iter()
+        .next()
+        .map_or(JsonValue::Null, convert))
+}
+
+/// Convert a [`Yaml`] value to a [`JsonValue`]
+fn convert(value: Yaml) -> JsonValue {
+    match value {
+        Yaml::String(value) => JsonValue::String(value),
+        Yaml::Integer(value) => JsonValue::Number(value as f64),
+        Yaml::Real(value) => JsonValue::Number(value.parse().unwrap_or(f64::NAN)),
+        Yaml::Boolean(value) => JsonValue::Boolean(value),
+        Yaml::Array(values) => JsonValue::Array(values.into_iter().map(convert).collect()),
+        Yaml::Hash(hash) => {
+            let object: HashMap<_, _> = hash
+                .into_iter()
+                .map(|(key, value)| (key_to_string(key), convert(value)))
+                .collect();
+            JsonValue::Object(object)
+        }
+        Yaml::Alias(_) | Yaml::Null | Yaml::BadValue => JsonValue::Null,
+    }
+}
+
+/// Convert a mapping key to a string
+fn key_to_string(key: Yaml) -> String {
+    match key {
+        Yaml::String(value) | Yaml::Real(value) => value,
+        Yaml::Integer(value) => value.to_string(),
+        Yaml::Boolean(value) => value.to_string(),
+        _ => String::from("~"),
+    }
+}
+
+/// Find the position (line and column) of all the keys of a YAML file
+///
+/// Nested keys are flattened with dots, like in [`TranslationData`](crate::parser::TranslationData).
+/// Only the first document is scanned, and scanning stops at the first syntax error.
+pub(super) fn positions(content: &str) -> BTreeMap<String, (usize, usize)> {
+    let mut receiver = PositionReceiver {
+        stack: Vec::new(),
+        positions: BTreeMap::new(),
+        done: false,
+    };
+    let _ = Parser::new_from_str(content).load(&mut receiver, false);
+
+    receiver.positions
+}
+
+/// Collection being scanned by the [`PositionReceiver`]
+enum Node {
+    /// Mapping with its path (`None` if keys are not recorded) and the path of the
+    /// current key (`None` while waiting for a key)
+    Mapping {
+        path: Option<String>,
+        key: Option<Option<String>>,
+    },
+    Sequence,
+}
+
+struct PositionReceiver {
+    stack: Vec<Node>,
+    positions: BTreeMap<String, (usize, usize)>,
+    done: bool,
+}
+
+impl PositionReceiver {
+    /// Start a value, returning the path under which its keys are recorded
+    fn value(&mut self) -> Option<String> {
+        match self.stack.last_mut() {
+            None => Some(String::new()),
+            Some(Node::Sequence) => None,
+            Some(Node::Mapping { key, .. }) => match key.take() {
+                Some(path) => path,
+                // Complex mapping key, keys inside it are ignored
+                None => {
+                    *key = Some(None);
+                    None
+                }
+            },
+        }
+    }
+}
+
+impl MarkedEventReceiver for PositionReceiver {
+    fn on_event(&mut self, event: Event, marker: Marker) {
+        if self.done {
+            return;
+        }
+
+        match event {
+            Event::Scalar(value, ..) => match self.stack.last_mut() {
+                Some(Node::Mapping {
+                    path,
+                    key: key @ None,
+                }) => {
+                    let path = path.as_ref().map(|path| match path.as_str() {
+                        "" => value,
+                        path => format!("{}.{}", path, value),
+                    });
+                    if let Some(path) = &path {
+                        self.positions
+                            .entry(path.clone())
+                            .or_insert((marker.line(), marker.col() + 1));
+                    }
+                    *key = Some(path);
+                }
+                _ => {
+                    self.value();
+                }
+            },
+            Event::Alias(_) => {
+                self.value();
+            }
+            Event::MappingStart(..) => {
+                let path = self.value();
+                self.stack.push(Node::Mapping { path, key: None });
+            }
+            Event::SequenceStart(..) => {
+                self.value();
+                self.stack.push(Node::Sequence);
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                self.stack.pop();
+            }
+            Event::DocumentEnd => self.done = true,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use tinyjson::JsonValue;
+
+    use super::{parse, positions};
+    use crate::{
+        builder::LanguageId,
+        error::{BuildError, Location, ParseError},
+        parser::TranslationData,
+        source::Locator,
+    };
+
+    #[test]
+    fn yaml_nested_plural() -> Result<(), Box<dyn std::error::Error>> {
+        let en = parse(
+            Path::new("en.yaml"),
+            r#"
+# Comments are allowed
+hello: Hello world!
+auth:
+  login:
+    welcome: "Welcome back {name}!"
+items:
+  one: "{count} item"
+  other: "{count} items"
+description: >
+  A long text
+  on multiple lines.
+"#,
+        )?;
+        let fr = parse(Path::new("fr.yml"), "hello: Bonjour le monde !")?;
+
+        let mut parsed = TranslationData::from_fallback(en)?;
+        parsed.parse_file(LanguageId("fr".into()), fr)?;
+
+        assert_eq!(parsed.keys.len(), 4);
+        assert!(parsed.keys.contains_key("auth.login.welcome"));
+        assert!(parsed.keys.contains_key("items"));
+        assert!(parsed.keys.contains_key("description"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn yaml_invalid_value() {
+        let file = parse(Path::new("en.yaml"), "count: 42").unwrap();
+        assert!(matches!(file, JsonValue::Object(_)));
+        assert!(TranslationData::from_fallback(file).is_err());
+    }
+
+    #[test]
+    fn yaml_syntax_error() {
+        let result = parse(Path::new("en.yaml"), "hello: \"unclosed");
+        match result {
+            Err(BuildError::SourceParse { line, .. }) => assert_eq!(line, Some(1)),
+            _ => panic!("expected a YAML parse error"),
+        }
+    }
+
+    #[test]
+    fn yaml_positions() {
+        let content = r#"
+hello: Hello world!
+nested:
+  key: [a, { ignored: 1 }]
+  "quoted": true
+  list:
+    - ignored: 1
+? [complex]
+: value
+last: 42
+---
+other: ignored
+"#;
+        let positions = positions(content);
+
+        assert_eq!(positions["hello"], (2, 1));
+        assert_eq!(positions["nested"], (3, 1));
+        assert_eq!(positions["nested.key"], (4, 3));
+        assert_eq!(positions["nested.quoted"], (5, 3));
+        assert_eq!(positions["nested.list"], (6, 3));
+        assert_eq!(positions["last"], (10, 1));
+        assert_eq!(positions.len(), 6);
+    }
+
+    #[test]
+    fn yaml_locate_error() {
+        let content = "hello: Hello\nitems:\n  one: \"{count} item\"\n  other: 42\n";
+        let locator = Locator {
+            path: PathBuf::from("locales/fr.yaml"),
+            positions: positions(content),
+            lines: content.lines().map(str::to_string).collect(),
+        };
+
+        let error = TranslationData::from_fallback(parse(Path::new("fr.yaml"), content).unwrap())
+            .unwrap_err();
+        assert!(matches!(&error, ParseError::InvalidValue { key } if key == "items.other"));
+
+        match locator.locate(error) {
+            BuildError::Located { location, .. } => assert_eq!(
+                location,
+                Location {
+                    line: 4,
+                    column: 3,
+                    excerpt: "  other: 42".to_string()
+                }
+            ),
+            _ => panic!("expected a located error"),
+        }
+    }
+}
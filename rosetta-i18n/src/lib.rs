@@ -17,12 +17,29 @@
 //! This crate provide serialization and deserialization of languages types with Serde.
 //! The `serde` feature must be enabled.
 //!
+//! ## `no_std` support
+//! This crate is `no_std` and only requires `core` when default features are disabled.
+//! The following features are available:
+//! - `std` (enabled by default): enables the `alloc` feature.
+//! - `alloc`: enables language tag validation, `Accept-Language` parsing and runtime formatting,
+//!   which require an allocator.
+//!
+//! Code generated by `rosetta-build` with the `no_std` option only uses `core`.
+//!
 //! [documentation]: https://baptiste0928.github.io/rosetta/
+#![no_std]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
-use std::borrow::Cow;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
 
-#[doc(hidden)]
+pub mod lookup;
+pub mod negotiate;
 pub mod provider;
 #[cfg(feature = "serde")]
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
@@ -57,14 +74,39 @@ pub trait Language: Sized {
     /// Initialize this type from a [`LanguageId`].
     ///
     /// The method returns [`None`] if the provided language id is not supported
-    /// by the struct.
+    /// by the struct. Types generated by `rosetta-build` canonicalize the provided
+    /// language id, so `pt-br` and `pt_BR` both match the `pt-BR` language.
     fn from_language_id(language_id: &LanguageId) -> Option<Self>;
     /// Convert this struct to a [`LanguageId`].
-    fn language_id(&self) -> LanguageId;
+    fn language_id(&self) -> LanguageId<'_>;
     /// Get the fallback language of this type.
     ///
     /// This fallback value can be used like a default value.
     fn fallback() -> Self;
+
+    /// Get all the languages supported by this type, e.g. to build a language picker.
+    ///
+    /// Types generated by `rosetta-build` return all their languages, sorted by language
+    /// identifier. The default implementation returns an empty slice.
+    fn all() -> &'static [Self]
+    where
+        Self: 'static,
+    {
+        &[]
+    }
+
+    /// Select the best supported language from an `Accept-Language` HTTP header value.
+    ///
+    /// Returns the [`fallback`] language if no requested language is supported.
+    /// See the [`negotiate`] module for more information.
+    ///
+    /// [`fallback`]: Language::fallback
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn negotiate(accept_language: &str) -> Self {
+        let requested = negotiate::parse_accept_language(accept_language);
+        negotiate::negotiate(&requested)
+    }
 }
 
 /// Generic language type that implement the [`Language`] trait.
@@ -76,14 +118,17 @@ pub trait Language: Sized {
 /// The [`fallback`] method of the [`Language`] trait is not implemented and will panic if called.
 ///
 /// [`fallback`]: Language::fallback
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub struct GenericLanguage(String);
 
+#[cfg(feature = "alloc")]
 impl Language for GenericLanguage {
     fn from_language_id(language_id: &LanguageId) -> Option<Self> {
         Some(Self(language_id.value().into()))
     }
 
-    fn language_id(&self) -> LanguageId {
+    fn language_id(&self) -> LanguageId<'_> {
         LanguageId::new(&self.0)
     }
 
@@ -92,66 +137,115 @@ impl Language for GenericLanguage {
     }
 }
 
-/// ISO 639-1 language identifier.
+/// BCP 47 language tag.
 ///
-/// This type holds a string representing a language in the [ISO 693-1] format (two-letter code).
-/// The inner value is stored in a [`Cow`] to avoid allocation when possible.
+/// This type holds a string representing a language in the [BCP 47] format, such as `fr`,
+/// `pt-BR` or `zh-Hant`. A language tag is composed of a language subtag, followed by
+/// optional script, region and variants subtags.
+/// The inner value is stored in a `Cow` to avoid allocation when possible, or in
+/// a `&str` if the `alloc` feature is disabled.
 ///
 /// ## Validation
 /// The type inner value is not validated unless the [`validate`] method is used to initialize the instance.
 /// Generally, you should use this method to initialize this type.
 ///
-/// The performed validation only checks that the provided *looks like* an [ISO 693-1]language identifier
-/// (2 character alphanumeric ascii string).
+/// The performed validation checks that the provided value is a well-formed [BCP 47] language tag
+/// with a two or three letters language subtag, and converts it to its canonical form
+/// (e.g. `pt_br` becomes `pt-BR`). Extensions and private use subtags are not supported.
 ///
 /// ## Serde support
 /// This type implements the `Serialize` and `Deserialize` traits if the `serde` feature is enabled.
-/// Deserialization will fail if the value is not an ISO 639-1 language identifier.
+/// Deserialization will fail if the value is not a valid BCP 47 language tag.
 ///
 /// ## Example
 /// ```
 /// use rosetta_i18n::LanguageId;
 ///
-/// let language_id = LanguageId::new("fr");
+/// let language_id = LanguageId::borrowed("fr");
 /// assert_eq!(language_id.value(), "fr");
+/// ```
 ///
-/// let language_id = LanguageId::validate("fr");
-/// assert!(language_id.is_some());
+/// Validation requires the `alloc` feature:
+#[cfg_attr(feature = "alloc", doc = "```")]
+#[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+/// # use rosetta_i18n::LanguageId;
+/// let language_id = LanguageId::validate("pt_br").unwrap();
+/// assert_eq!(language_id.value(), "pt-BR");
+/// assert_eq!(language_id.language(), "pt");
+/// assert_eq!(language_id.region(), Some("BR"));
 /// ```
 ///
-/// [ISO 693-1]: https://en.wikipedia.org/wiki/ISO_639-1
+/// [BCP 47]: https://www.rfc-editor.org/info/bcp47
 /// [`validate`]: LanguageId::validate
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct LanguageId<'a>(Cow<'a, str>);
+pub struct LanguageId<'a>(Inner<'a>);
+
+#[cfg(feature = "alloc")]
+type Inner<'a> = Cow<'a, str>;
+#[cfg(not(feature = "alloc"))]
+type Inner<'a> = &'a str;
 
 impl<'a> LanguageId<'a> {
     /// Initialize a new valid [`LanguageId`].
     ///
     /// Unlike [`new`], this method ensures that the provided
-    /// value is a valid [ISO 693-1] encoded language id.
+    /// value is a valid [BCP 47] language tag. The returned value
+    /// is converted to its canonical form.
     ///
     /// ```
     /// # use rosetta_i18n::LanguageId;
-    /// assert!(LanguageId::validate("fr").is_some());
+    /// assert_eq!(LanguageId::validate("fr").unwrap().value(), "fr");
+    /// assert_eq!(LanguageId::validate("ZH-hant-tw").unwrap().value(), "zh-Hant-TW");
+    /// assert_eq!(LanguageId::validate("es-419").unwrap().value(), "es-419");
+    /// assert_eq!(LanguageId::validate("de-CH-1996").unwrap().value(), "de-CH-1996");
     /// assert!(LanguageId::validate("invalid").is_none());
+    /// assert!(LanguageId::validate("fr-").is_none());
     /// ```
     ///
     /// [`new`]: LanguageId::new
-    /// [ISO 693-1]: https://en.wikipedia.org/wiki/ISO_639-1
+    /// [BCP 47]: https://www.rfc-editor.org/info/bcp47
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
     pub fn validate(value: &str) -> Option<Self> {
-        let valid_length = value.len() == 2;
-        let ascii_alphabetic = value.chars().all(|c| c.is_ascii_alphabetic());
+        let mut subtags = value.split(['-', '_']);
+
+        let language = subtags.next()?;
+        if !matches!(language.len(), 2..=3) || !is_alphabetic(language) {
+            return None;
+        }
+
+        let mut canonical = language.to_ascii_lowercase();
+        let mut next = subtags.next();
+
+        if let Some(script) = next.filter(|subtag| is_script(subtag)) {
+            canonical.push('-');
+            canonical.push_str(&script[..1].to_ascii_uppercase());
+            canonical.push_str(&script[1..].to_ascii_lowercase());
+            next = subtags.next();
+        }
+
+        if let Some(region) = next.filter(|subtag| is_region(subtag)) {
+            canonical.push('-');
+            canonical.push_str(&region.to_ascii_uppercase());
+            next = subtags.next();
+        }
+
+        while let Some(variant) = next {
+            if !is_variant(variant) {
+                return None;
+            }
 
-        if valid_length && ascii_alphabetic {
-            Some(Self(Cow::Owned(value.to_ascii_lowercase())))
-        } else {
-            None
+            canonical.push('-');
+            canonical.push_str(&variant.to_ascii_lowercase());
+            next = subtags.next();
         }
+
+        Some(Self(Cow::Owned(canonical)))
     }
 
     /// Initialize a new [`LanguageId`] from a string.
     ///
-    /// The provided value must be an [ISO 693-1] encoded language id.
+    /// The provided value must be a [BCP 47] language tag in its canonical form.
     /// If you want to validate the value, use [`validate`] instead.
     ///
     /// ```
@@ -160,19 +254,144 @@ impl<'a> LanguageId<'a> {
     /// assert_eq!(language_id.value(), "en");
     /// ```
     ///
-    /// [ISO 693-1]: https://en.wikipedia.org/wiki/ISO_639-1
+    /// [BCP 47]: https://www.rfc-editor.org/info/bcp47
     /// [`validate`]: LanguageId::validate
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
     pub fn new(value: impl Into<Cow<'a, str>>) -> Self {
         Self(value.into())
     }
 
+    /// Initialize a new [`LanguageId`] from a borrowed string.
+    ///
+    /// This is the same as [`new`], but is available without the `alloc` feature
+    /// and can be used in constants.
+    ///
+    /// ```
+    /// # use rosetta_i18n::LanguageId;
+    /// const ENGLISH: LanguageId<'static> = LanguageId::borrowed("en");
+    /// assert_eq!(ENGLISH.value(), "en");
+    /// ```
+    ///
+    /// [`new`]: LanguageId::new
+    pub const fn borrowed(value: &'a str) -> Self {
+        #[cfg(feature = "alloc")]
+        let value = Cow::Borrowed(value);
+
+        Self(value)
+    }
+
     /// Return a reference of the inner value.
+    #[cfg_attr(not(feature = "alloc"), allow(clippy::needless_borrow))]
     pub fn value(&self) -> &str {
         &self.0
     }
 
     /// Convert the type into a [`String`].
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
     pub fn into_inner(self) -> String {
         self.0.into_owned()
     }
+
+    /// Check if the value matches a canonical language tag, ignoring case and separators.
+    ///
+    /// This gives the same result as comparing the [`validate`] result with the tag,
+    /// without allocating. Types generated by `rosetta-build` use it to match languages.
+    ///
+    /// ```
+    /// # use rosetta_i18n::LanguageId;
+    /// assert!(LanguageId::borrowed("pt_br").matches("pt-BR"));
+    /// assert!(LanguageId::borrowed("ZH-hant").matches("zh-Hant"));
+    /// assert!(!LanguageId::borrowed("pt").matches("pt-BR"));
+    /// ```
+    ///
+    /// [`validate`]: LanguageId::validate
+    pub fn matches(&self, tag: &str) -> bool {
+        let value = self.value();
+
+        value.len() == tag.len()
+            && value
+                .bytes()
+                .zip(tag.bytes())
+                .all(|(a, b)| a.eq_ignore_ascii_case(&b) || (a == b'_' && b == b'-'))
+    }
+
+    /// Return the language subtag.
+    ///
+    /// ```
+    /// # use rosetta_i18n::LanguageId;
+    /// assert_eq!(LanguageId::borrowed("pt-BR").language(), "pt");
+    /// ```
+    pub fn language(&self) -> &str {
+        self.subtags().next().unwrap_or_default()
+    }
+
+    /// Return the script subtag, if any.
+    ///
+    /// ```
+    /// # use rosetta_i18n::LanguageId;
+    /// assert_eq!(LanguageId::borrowed("zh-Hant-TW").script(), Some("Hant"));
+    /// assert_eq!(LanguageId::borrowed("zh-TW").script(), None);
+    /// ```
+    pub fn script(&self) -> Option<&str> {
+        self.subtags().skip(1).find(|subtag| is_script(subtag))
+    }
+
+    /// Return the region subtag, if any.
+    ///
+    /// ```
+    /// # use rosetta_i18n::LanguageId;
+    /// assert_eq!(LanguageId::borrowed("zh-Hant-TW").region(), Some("TW"));
+    /// assert_eq!(LanguageId::borrowed("zh-Hant").region(), None);
+    /// ```
+    pub fn region(&self) -> Option<&str> {
+        self.subtags().skip(1).find(|subtag| is_region(subtag))
+    }
+
+    /// Return an iterator over the variants subtags.
+    ///
+    /// ```
+    /// # use rosetta_i18n::LanguageId;
+    /// let language_id = LanguageId::borrowed("de-CH-1996");
+    /// assert_eq!(language_id.variants().collect::<Vec<_>>(), vec!["1996"]);
+    /// ```
+    pub fn variants(&self) -> impl Iterator<Item = &str> {
+        self.subtags().skip(1).filter(|subtag| is_variant(subtag))
+    }
+
+    /// Iterate over the subtags of the value.
+    fn subtags(&self) -> impl Iterator<Item = &str> {
+        self.0.split(['-', '_'])
+    }
+}
+
+fn is_alphabetic(subtag: &str) -> bool {
+    subtag.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// Script subtag (4 letters, ISO 15924)
+fn is_script(subtag: &str) -> bool {
+    subtag.len() == 4 && is_alphabetic(subtag)
+}
+
+/// Region subtag (2 letters ISO 3166-1 or 3 digits UN M.49 code)
+fn is_region(subtag: &str) -> bool {
+    match subtag.len() {
+        2 => is_alphabetic(subtag),
+        3 => subtag.chars().all(|c| c.is_ascii_digit()),
+        _ => false,
+    }
+}
+
+/// Variant subtag (5 to 8 alphanumeric characters, or 4 characters starting with a digit)
+fn is_variant(subtag: &str) -> bool {
+    let alphanumeric = subtag.chars().all(|c| c.is_ascii_alphanumeric());
+    let starts_with_digit = subtag.starts_with(|c: char| c.is_ascii_digit());
+
+    match subtag.len() {
+        4 => alphanumeric && starts_with_digit,
+        5..=8 => alphanumeric,
+        _ => false,
+    }
 }
@@ -0,0 +1,133 @@
+//! Runtime lookup of translation keys.
+//!
+//! Types generated by `rosetta-build` with the `runtime_lookup` option provide a `get` method
+//! to get the value of a key from its name (e.g. from a database, a template or an API error code),
+//! and a `get_formatted` method for keys with parameters, which are given as a list of name and value pairs.
+//!
+//! ## Example
+//! ```ignore
+//! assert_eq!(Lang::En.get("hello"), Some("Hello world!"));
+//! assert_eq!(
+//!     Lang::En.get_formatted("hello_name", &[("name", &"John")]),
+//!     Some("Hello John!".to_string())
+//! );
+//! ```
+//!
+//! Code generated with the `no_std` option provides a `write_formatted` method instead of
+//! `get_formatted`, which writes the value into a [`core::fmt::Write`] without allocating.
+//!
+//! This module contains helpers used by the generated code.
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::fmt::{self, Display, Write};
+
+/// Format a translation value with parameters given by name.
+///
+/// Parameters are written `{name}` in the value, and literal braces are escaped
+/// by doubling them (`{{` and `}}`). Returns `None` if a parameter is missing.
+///
+/// ```
+/// use rosetta_i18n::lookup::format;
+///
+/// let formatted = format("{{{name}}} is {age}", &[("name", &"John"), ("age", &42)]);
+/// assert_eq!(formatted, Some("{John} is 42".to_string()));
+/// assert_eq!(format("{name} is {age}", &[("name", &"John")]), None);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn format(value: &str, args: &[(&str, &dyn Display)]) -> Option<String> {
+    let mut output = String::with_capacity(value.len());
+    write(&mut output, value, args)?.ok()?;
+
+    Some(output)
+}
+
+/// Write a translation value with parameters given by name.
+///
+/// This is the same as [`format`], without allocating. Returns `None` if a parameter
+/// is missing, in which case nothing is written.
+///
+/// ```
+/// use rosetta_i18n::lookup::write;
+///
+/// let mut output = String::new();
+/// let result = write(&mut output, "{name} is {age}", &[("name", &"John"), ("age", &42)]);
+/// assert_eq!(result, Some(Ok(())));
+/// assert_eq!(output, "John is 42");
+/// ```
+pub fn write(
+    output: &mut dyn Write,
+    value: &str,
+    args: &[(&str, &dyn Display)],
+) -> Option<fmt::Result> {
+    // Check the value before writing anything
+    for segment in segments(value) {
+        if let Segment::Parameter(name) = segment? {
+            args.iter().find(|(arg, _)| *arg == name)?;
+        }
+    }
+
+    let result = segments(value).try_for_each(|segment| match segment {
+        Some(Segment::Literal(literal)) => output.write_str(literal),
+        Some(Segment::Parameter(name)) => match args.iter().find(|(arg, _)| *arg == name) {
+            Some((_, arg)) => write!(output, "{}", arg),
+            None => Ok(()),
+        },
+        None => Ok(()),
+    });
+
+    Some(result)
+}
+
+/// Segment of a translation value
+enum Segment<'a> {
+    Literal(&'a str),
+    Parameter(&'a str),
+}
+
+/// Iterate over the segments of a value, yielding `None` if a brace is unbalanced
+fn segments(value: &str) -> impl Iterator<Item = Option<Segment<'_>>> {
+    let mut rest = value;
+
+    core::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+
+        let segment = match rest.find(['{', '}']) {
+            Some(0) if rest.starts_with("{{") || rest.starts_with("}}") => {
+                let (literal, next) = (&rest[..1], &rest[2..]);
+                rest = next;
+                Some(Segment::Literal(literal))
+            }
+            Some(0) if rest.starts_with('{') => match rest.find('}') {
+                Some(end) => {
+                    let name = &rest[1..end];
+                    rest = &rest[end + 1..];
+                    Some(Segment::Parameter(name))
+                }
+                None => {
+                    rest = "";
+                    None
+                }
+            },
+            Some(0) => {
+                rest = "";
+                None
+            }
+            Some(index) => {
+                let (literal, next) = rest.split_at(index);
+                rest = next;
+                Some(Segment::Literal(literal))
+            }
+            None => {
+                let literal = rest;
+                rest = "";
+                Some(Segment::Literal(literal))
+            }
+        };
+
+        Some(segment)
+    })
+}
@@ -0,0 +1,192 @@
+//! Language negotiation.
+//!
+//! This module provides helpers to select the best supported language from a list
+//! of languages requested by a user, such as the value of an `Accept-Language` HTTP header.
+//!
+//! The [`Language::negotiate`] method is a shortcut for these helpers.
+//!
+//! ## Matching
+//! Requested languages are tried in order of preference. A requested language matches
+//! a supported language if they are identical, or if the supported language is a prefix
+//! of the requested language: `fr-Latn-CH` matches `fr-Latn`, then `fr`. This follows the
+//! *lookup* scheme defined in [RFC 4647].
+//!
+//! If no requested language matches, the [fallback] language is returned.
+//!
+//! ## Example
+//! Parsing the `Accept-Language` header requires the `alloc` feature.
+//!
+#![cfg_attr(feature = "alloc", doc = "```")]
+#![cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+//! use rosetta_i18n::{negotiate, GenericLanguage, Language};
+//!
+//! let requested = negotiate::parse_accept_language("fr-CH, fr;q=0.9, en;q=0.8");
+//! let values: Vec<_> = requested.iter().map(|language_id| language_id.value()).collect();
+//! assert_eq!(values, vec!["fr-CH", "fr", "en"]);
+//!
+//! let language: GenericLanguage = negotiate::negotiate(&requested);
+//! assert_eq!(language.language_id().value(), "fr-CH");
+//! ```
+//!
+//! [RFC 4647]: https://www.rfc-editor.org/rfc/rfc4647#section-3.4
+//! [fallback]: Language::fallback
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{Language, LanguageId};
+
+/// Parse the value of an `Accept-Language` HTTP header.
+///
+/// Returned languages are sorted by decreasing quality value (`q` parameter),
+/// and have the same order as in the header if they have the same quality.
+/// Invalid language tags, wildcards (`*`) and languages with a quality of `0`
+/// are ignored.
+///
+/// ```
+/// # use rosetta_i18n::negotiate::parse_accept_language;
+/// let requested = parse_accept_language("en;q=0.5, pt-br, *;q=0.1, de;q=0");
+/// let values: Vec<_> = requested.iter().map(|language_id| language_id.value()).collect();
+/// assert_eq!(values, vec!["pt-BR", "en"]);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn parse_accept_language(header: &str) -> Vec<LanguageId<'static>> {
+    let mut languages: Vec<(LanguageId, f32)> = header
+        .split(',')
+        .filter_map(|entry| {
+            let mut params = entry.split(';').map(str::trim);
+            let language_id = LanguageId::validate(params.next()?)?;
+
+            let quality = match params.find_map(|param| param.strip_prefix("q=")) {
+                Some(quality) => quality.parse::<f32>().ok()?,
+                None => 1.0,
+            };
+
+            if quality > 0.0 && quality <= 1.0 {
+                Some((language_id, quality))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    // Stable sort to keep header order for equal quality values
+    languages.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(core::cmp::Ordering::Equal));
+    languages
+        .into_iter()
+        .map(|(language_id, _)| language_id)
+        .collect()
+}
+
+/// Select the best supported language from a list of requested languages.
+///
+/// Requested languages must be sorted by order of preference. See the
+/// [module documentation](self) for the matching rules.
+pub fn negotiate<L: Language>(requested: &[LanguageId]) -> L {
+    requested
+        .iter()
+        .find_map(lookup)
+        .unwrap_or_else(L::fallback)
+}
+
+/// Find a supported language matching the requested language or one of its prefixes.
+fn lookup<L: Language>(requested: &LanguageId) -> Option<L> {
+    let mut value = requested.value();
+
+    loop {
+        if let Some(language) = L::from_language_id(&LanguageId::borrowed(value)) {
+            return Some(language);
+        }
+
+        value = &value[..value.rfind('-')?];
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::{string::String, vec, vec::Vec};
+
+    use super::{negotiate, parse_accept_language};
+    use crate::{Language, LanguageId};
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Lang {
+        En,
+        Fr,
+        FrCa,
+    }
+
+    impl Language for Lang {
+        fn from_language_id(language_id: &LanguageId) -> Option<Self> {
+            match language_id.value() {
+                "en" => Some(Self::En),
+                "fr" => Some(Self::Fr),
+                "fr-CA" => Some(Self::FrCa),
+                _ => None,
+            }
+        }
+
+        fn language_id(&self) -> LanguageId<'_> {
+            match self {
+                Self::En => LanguageId::new("en"),
+                Self::Fr => LanguageId::new("fr"),
+                Self::FrCa => LanguageId::new("fr-CA"),
+            }
+        }
+
+        fn fallback() -> Self {
+            Self::En
+        }
+    }
+
+    fn values(header: &str) -> Vec<String> {
+        parse_accept_language(header)
+            .into_iter()
+            .map(LanguageId::into_inner)
+            .collect()
+    }
+
+    #[test]
+    fn parse_quality() {
+        assert_eq!(
+            values("fr-CH, fr;q=0.9, en;q=0.8, de;q=0.7, *;q=0.5"),
+            vec!["fr-CH", "fr", "en", "de"]
+        );
+        assert_eq!(values("en;q=0.5, fr"), vec!["fr", "en"]);
+        assert_eq!(values("en;q=0.8, de;q=0.8"), vec!["en", "de"]);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(values(""), Vec::<String>::new());
+        assert_eq!(
+            values("invalid, fr;q=2, de;q=abc, en;q=0"),
+            Vec::<String>::new()
+        );
+        assert_eq!(values(" zh_hant_tw ; q=0.3 "), vec!["zh-Hant-TW"]);
+    }
+
+    #[test]
+    fn negotiate_exact() {
+        let requested = parse_accept_language("de, fr-CA;q=0.9, fr;q=0.8");
+        assert_eq!(negotiate::<Lang>(&requested), Lang::FrCa);
+    }
+
+    #[test]
+    fn negotiate_prefix() {
+        let requested = parse_accept_language("fr-CH, en;q=0.8");
+        assert_eq!(negotiate::<Lang>(&requested), Lang::Fr);
+
+        let requested = parse_accept_language("fr-Latn-CA");
+        assert_eq!(negotiate::<Lang>(&requested), Lang::Fr);
+    }
+
+    #[test]
+    fn negotiate_fallback() {
+        let requested = parse_accept_language("de-CH, es");
+        assert_eq!(negotiate::<Lang>(&requested), Lang::En);
+        assert_eq!(Lang::negotiate(""), Lang::En);
+        assert_eq!(Lang::negotiate("fr-CA, fr;q=0.9"), Lang::FrCa);
+    }
+}
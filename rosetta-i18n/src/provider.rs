@@ -12,7 +12,8 @@
 //! ## Implementing a provider
 //! If you need to support extra languages that are not in the default provider,
 //! you should create a type that implement [`LanguageProvider`]. This type
-//! can then be used as a generic parameter of language type created by `rosetta-build`.
+//! can then be registered with the `provider` option of `rosetta-build`, and
+//! will be used by the generated code to select plural forms.
 //!
 //! Example implementation:
 //! ```
@@ -35,6 +36,10 @@
 //!             _ => PluralCategory::Other
 //!         }
 //!     }
+//!
+//!     fn plural_categories(&self) -> &'static [PluralCategory] {
+//!         &[PluralCategory::One, PluralCategory::Other]
+//!     }
 //! }
 //! ```
 //!
@@ -73,6 +78,17 @@ pub trait LanguageProvider: Sized {
 
     /// Select the appropriate [`PluralCategory`] for a given number.
     fn plural(&self, number: u64) -> PluralCategory;
+
+    /// List the [`PluralCategory`] that can be returned by [`plural`].
+    ///
+    /// This is used by `rosetta-build` to check that plural keys provide
+    /// all the categories needed by the language. The default implementation
+    /// only returns [`PluralCategory::Other`].
+    ///
+    /// [`plural`]: LanguageProvider::plural
+    fn plural_categories(&self) -> &'static [PluralCategory] {
+        &[PluralCategory::Other]
+    }
 }
 
 /// CLDR Plural category.
@@ -80,7 +96,7 @@ pub trait LanguageProvider: Sized {
 /// This type represent a plural category as defined in [Unicode CLDR Plural Rules].
 ///
 /// [Unicode CLDR Plural Rules]: https://cldr.unicode.org/index/cldr-spec/plural-rules
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PluralCategory {
     /// Zero plural category.
     ///
@@ -115,8 +131,9 @@ pub enum PluralCategory {
 /// few common latin languages.** You should implement [`LanguageProvider`]
 /// yourself if you want to support more languages.
 ///
-/// The [`En`](DefaultProvider::En) variant is used when an unknown
-/// [`LanguageId`] is provided.
+/// The provider is selected from the language subtag of the [`LanguageId`]
+/// (e.g. `fr-CA` uses [`Fr`](DefaultProvider::Fr)). The [`En`](DefaultProvider::En)
+/// variant is used when an unknown [`LanguageId`] is provided.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DefaultProvider {
     /// English
@@ -133,7 +150,7 @@ pub enum DefaultProvider {
 
 impl LanguageProvider for DefaultProvider {
     fn from_id(language_id: &LanguageId) -> Self {
-        match language_id.value() {
+        match language_id.language() {
             "es" => Self::Es,
             "fr" => Self::Fr,
             "de" => Self::De,
@@ -154,4 +171,8 @@ impl LanguageProvider for DefaultProvider {
             },
         }
     }
+
+    fn plural_categories(&self) -> &'static [PluralCategory] {
+        &[PluralCategory::One, PluralCategory::Other]
+    }
 }
@@ -26,7 +26,7 @@
 //!
 //! [`Language`]: crate::Language
 
-use std::borrow::Cow;
+use alloc::{borrow::Cow, format};
 
 use serde::{de, ser};
 
@@ -51,7 +51,7 @@ impl<'de> de::Deserialize<'de> for LanguageId<'de> {
         match Self::validate(&value) {
             Some(language_id) => Ok(language_id),
             None => Err(de::Error::custom(format!(
-                "`{}` is not a valid ISO 693-1 language id",
+                "`{}` is not a valid BCP 47 language tag",
                 value
             ))),
         }
@@ -159,7 +159,7 @@ mod tests {
     fn serde_invalid_language_id() {
         assert_de_tokens_error::<LanguageId>(
             &[Token::String("invalid")],
-            "`invalid` is not a valid ISO 693-1 language id",
+            "`invalid` is not a valid BCP 47 language tag",
         )
     }
 
@@ -176,7 +176,7 @@ mod tests {
                 }
             }
 
-            fn language_id(&self) -> LanguageId {
+            fn language_id(&self) -> LanguageId<'_> {
                 LanguageId::new("en")
             }
 
@@ -220,7 +220,7 @@ mod tests {
                 }
             }
 
-            fn language_id(&self) -> LanguageId {
+            fn language_id(&self) -> LanguageId<'_> {
                 LanguageId::new("en")
             }
 
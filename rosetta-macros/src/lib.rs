@@ -0,0 +1,200 @@
+//! Procedural macros for the Rosetta i18n library.
+//!
+//! # Usage
+//! The [`translations!`] macro generates translations without build script.
+//! It accepts the same sources as [`rosetta-build`](https://docs.rs/rosetta-build),
+//! and generates the same code.
+//!
+//! ```ignore
+//! mod translations {
+//!     rosetta_macros::translations!(
+//!         fallback = "en",
+//!         sources = {
+//!             "en" => "locales/en.json",
+//!             "fr" => "locales/fr.json",
+//!         },
+//!     );
+//! }
+//! ```
+//!
+//! Please read the [documentation] for more information.
+//!
+//! [documentation]: https://baptiste0928.github.io/rosetta/
+
+use std::{env, path::PathBuf};
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    Ident, LitBool, LitStr, Token,
+};
+
+/// Generate translations from source files.
+///
+/// The following options are supported:
+/// - `fallback` (required): the fallback language
+/// - `sources` (required): translation sources, as `"language" => "path"` pairs
+/// - `name`: name of the generated type (`Lang` by default)
+/// - `flatten_keys`: generate nested keys as flattened methods
+///
+/// Paths are relative to the crate root (the directory containing `Cargo.toml`).
+/// The crate is rebuilt when a source file changes.
+///
+/// ```ignore
+/// rosetta_macros::translations!(
+///     fallback = "en",
+///     sources = { "en" => "locales/en.json", "fr" => "locales/fr.json" },
+///     name = "Lang",
+/// );
+/// ```
+#[proc_macro]
+pub fn translations(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as TranslationsInput);
+
+    match input.generate() {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Options of the [`translations!`] macro
+///
+/// ```
+/// rosetta_macros::translations!(
+///     fallback = "en",
+///     sources = { "en" => "tests/locales/en.json" },
+/// );
+/// # assert_eq!(Lang::En.hello(), "Hello world!");
+/// ```
+///
+/// Missing and unknown options are compilation errors:
+///
+/// ```compile_fail
+/// rosetta_macros::translations!(sources = { "en" => "tests/locales/en.json" });
+/// ```
+///
+/// ```compile_fail
+/// rosetta_macros::translations!(
+///     fallback = "en",
+///     sources = { "en" => "tests/locales/en.json" },
+///     output = "lang.rs",
+/// );
+/// ```
+///
+/// Errors in source files are also compilation errors:
+///
+/// ```compile_fail
+/// rosetta_macros::translations!(
+///     fallback = "en",
+///     sources = { "en" => "tests/locales/invalid.json" },
+/// );
+/// ```
+struct TranslationsInput {
+    fallback: LitStr,
+    sources: Vec<(LitStr, LitStr)>,
+    name: Option<LitStr>,
+    flatten_keys: Option<LitBool>,
+}
+
+impl TranslationsInput {
+    /// Generate the translations and include the source files to track changes
+    fn generate(&self) -> syn::Result<TokenStream> {
+        let root = env::var("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .map_err(|error| syn::Error::new(Span::call_site(), error))?;
+
+        let mut builder = rosetta_build::config().fallback(self.fallback.value());
+        let mut paths = Vec::with_capacity(self.sources.len());
+        for (language, path) in &self.sources {
+            let path = root.join(path.value()).to_string_lossy().into_owned();
+            builder = builder.source(language.value(), path.clone());
+            paths.push(path);
+        }
+
+        if let Some(name) = &self.name {
+            builder = builder.name(name.value());
+        }
+        if let Some(flatten_keys) = &self.flatten_keys {
+            builder = builder.flatten_keys(flatten_keys.value);
+        }
+
+        let generated = builder
+            .generate_tokens()
+            .map_err(|error| syn::Error::new(Span::call_site(), error))?;
+
+        Ok(quote! {
+            #(const _: &[u8] = include_bytes!(#paths);)*
+
+            #generated
+        })
+    }
+}
+
+impl Parse for TranslationsInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut fallback = None;
+        let mut sources = None;
+        let mut name = None;
+        let mut flatten_keys = None;
+
+        while !input.is_empty() {
+            let option: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+
+            let duplicate = match option.to_string().as_str() {
+                "fallback" => fallback.replace(input.parse()?).is_some(),
+                "sources" => {
+                    let content;
+                    braced!(content in input);
+                    let parsed = Punctuated::<Source, Token![,]>::parse_terminated(&content)?;
+                    let parsed = parsed.into_iter().map(|source| (source.0, source.1));
+                    sources.replace(parsed.collect()).is_some()
+                }
+                "name" => name.replace(input.parse()?).is_some(),
+                "flatten_keys" => flatten_keys.replace(input.parse()?).is_some(),
+                _ => {
+                    let message = format!("unknown option `{}`", option);
+                    return Err(syn::Error::new(option.span(), message));
+                }
+            };
+
+            if duplicate {
+                let message = format!("duplicate option `{}`", option);
+                return Err(syn::Error::new(option.span(), message));
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        let missing = |option| {
+            let message = format!("missing `{}` option", option);
+            syn::Error::new(Span::call_site(), message)
+        };
+
+        Ok(Self {
+            fallback: fallback.ok_or_else(|| missing("fallback"))?,
+            sources: sources.ok_or_else(|| missing("sources"))?,
+            name,
+            flatten_keys,
+        })
+    }
+}
+
+/// Translation source, as a `"language" => "path"` pair
+struct Source(LitStr, LitStr);
+
+impl Parse for Source {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let language = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let path = input.parse()?;
+
+        Ok(Self(language, path))
+    }
+}
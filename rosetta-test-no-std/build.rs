@@ -0,0 +1,10 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    rosetta_build::config()
+        .source("fr", "../rosetta-test/locales/fr.json")
+        .source("en", "../rosetta-test/locales/en.json")
+        .fallback("en")
+        .no_std(true)
+        .generate()?;
+
+    Ok(())
+}
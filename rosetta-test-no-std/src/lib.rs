@@ -0,0 +1,7 @@
+//! Checks that code generated with the `no_std` option builds without the standard library.
+//!
+//! Build it for a target without `std` (e.g. `thumbv7em-none-eabihf`).
+
+#![no_std]
+
+rosetta_i18n::include_translations!();
@@ -1,8 +1,36 @@
+use std::{env, path::Path};
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    rosetta_build::config()
+        .source("fr", "locales/fr.json")
+        .source("fr-CA", "locales/fr-CA.json")
+        .source("en", "locales/en.json")
+        .fallback("en")
+        .fallback_chain("fr-CA", ["fr"])
+        .native_name("en", "English")
+        .native_name("fr", "Français")
+        .coverage_report(true)
+        .key_enum(true)
+        .runtime_lookup(true)
+        .generate()?;
+
+    rosetta_build::config()
+        .source("fr", "locales/fr.json")
+        .source("en", "locales/en.json")
+        .fallback("en")
+        .name("FlatLang")
+        .flatten_keys(true)
+        .output(Path::new(&env::var("OUT_DIR")?).join("rosetta_flat.rs"))
+        .generate()?;
+
     rosetta_build::config()
         .source("fr", "locales/fr.json")
         .source("en", "locales/en.json")
         .fallback("en")
+        .name("CoreLang")
+        .no_std(true)
+        .runtime_lookup(true)
+        .output(Path::new(&env::var("OUT_DIR")?).join("rosetta_core.rs"))
         .generate()?;
 
     Ok(())
@@ -12,6 +12,31 @@ mod tests {
 
     rosetta_i18n::include_translations!();
 
+    /// Same translations generated with flattened keys
+    #[allow(dead_code)]
+    mod flat {
+        include!(concat!(env!("OUT_DIR"), "/rosetta_flat.rs"));
+    }
+
+    /// Same translations generated with the `no_std` option
+    #[allow(dead_code)]
+    mod core_only {
+        include!(concat!(env!("OUT_DIR"), "/rosetta_core.rs"));
+    }
+
+    /// Same translations generated with the procedural macro
+    #[allow(dead_code)]
+    mod macros {
+        rosetta_macros::translations!(
+            fallback = "en",
+            sources = {
+                "en" => "locales/en.json",
+                "fr" => "locales/fr.json",
+            },
+            name = "MacroLang",
+        );
+    }
+
     assert_impl_all!(
         Lang: Language,
         Debug,
@@ -23,6 +48,7 @@ mod tests {
         Send,
         Sync
     );
+    assert_impl_all!(LangDisplayPrice<'static>: std::fmt::Display, Debug, Copy, Send, Sync);
 
     #[test]
     fn test_simple() {
@@ -32,14 +58,156 @@ mod tests {
 
     #[test]
     fn test_formatted() {
-        assert_eq!(Lang::En.hello_name("John"), "Hello John!");
-        assert_eq!(Lang::Fr.hello_name("John"), "Bonjour John !");
+        assert_eq!(Lang::En.hello_name("John").to_string(), "Hello John!");
+        assert_eq!(Lang::Fr.hello_name("John").to_string(), "Bonjour John !");
+    }
+
+    #[test]
+    fn test_formatted_display() {
+        use std::fmt::Write;
+
+        let value = Lang::Fr.hello_name("John");
+        let mut output = String::new();
+        write!(output, "[{}] [{}]", value, value).unwrap();
+        assert_eq!(output, "[Bonjour John !] [Bonjour John !]");
     }
 
     #[test]
     fn test_formatted_multiple() {
-        assert_eq!(Lang::En.display_age(30, "John"), "John is 30 years old.");
-        assert_eq!(Lang::Fr.display_age(30, "John"), "John a 30 ans.");
+        assert_eq!(
+            Lang::En.display_age(30, "John").to_string(),
+            "John is 30 years old."
+        );
+        assert_eq!(
+            Lang::Fr.display_age(30, "John").to_string(),
+            "John a 30 ans."
+        );
+    }
+
+    #[test]
+    fn test_formatted_typed() {
+        assert_eq!(
+            Lang::En.display_price("Bread", 1.5).to_string(),
+            "Bread costs 1.5 €."
+        );
+        assert_eq!(
+            Lang::Fr.display_price("Pain", 1.5).to_string(),
+            "Pain coûte 1.5 €."
+        );
+    }
+
+    #[test]
+    fn test_escaped_braces() {
+        assert_eq!(Lang::En.css_sample(), "a { color: red; }");
+        assert_eq!(
+            Lang::Fr.json_sample("John").to_string(),
+            r#"{"name": "John"}"#
+        );
+    }
+
+    #[test]
+    fn test_get() {
+        assert_eq!(Lang::En.get("hello"), Some("Hello world!"));
+        assert_eq!(Lang::Fr.get("auth.login.title"), Some("Connexion"));
+        assert_eq!(Lang::Fr.get("auth.logout"), Some("Log out"));
+        assert_eq!(Lang::FrCa.get("hello"), Lang::Fr.get("hello"));
+        assert_eq!(Lang::En.get("hello_name"), None);
+        assert_eq!(Lang::En.get("unknown"), None);
+
+        let args: &[(&str, &dyn std::fmt::Display)] = &[("name", &"John")];
+        assert_eq!(
+            Lang::Fr.get_formatted("hello_name", args),
+            Some("Bonjour John !".to_string())
+        );
+        assert_eq!(
+            Lang::En.get_formatted("hello", args),
+            Some("Hello world!".to_string())
+        );
+        assert_eq!(Lang::En.get_formatted("hello_name", &[]), None);
+        assert_eq!(Lang::En.get_formatted("unknown", args), None);
+    }
+
+    #[test]
+    fn test_key_enum() {
+        assert_eq!(Lang::Fr.translate(LangKey::Hello), "Bonjour le monde !");
+        assert_eq!(Lang::Fr.translate(LangKey::AuthLoginTitle), "Connexion");
+        assert_eq!(Lang::Fr.translate(LangKey::CssSample), "a { color: red; }");
+        assert_eq!(Lang::En.translate(LangKey::HelloName), "Hello {name}!");
+
+        let template = Lang::En.translate(LangKey::JsonSample);
+        assert_eq!(template, r#"{{"name": "{name}"}}"#);
+        assert_eq!(
+            rosetta_i18n::lookup::format(template, &[("name", &"John")]),
+            Some(Lang::En.json_sample("John").to_string())
+        );
+
+        let key = LangKey::DisplayAge;
+        assert_eq!(key.name(), "display_age");
+        assert_eq!(key.parameters(), ["age", "name"]);
+        assert!(LangKey::Hello.parameters().is_empty());
+
+        let keys = [
+            LangKey::AuthLoginTitle,
+            LangKey::AuthLoginWelcome,
+            LangKey::AuthLogout,
+            LangKey::CssSample,
+            LangKey::DisplayAge,
+            LangKey::DisplayPrice,
+            LangKey::FallbackKey,
+            LangKey::Hello,
+            LangKey::HelloName,
+            LangKey::JsonSample,
+        ];
+        for key in keys {
+            let value = Lang::Fr.translate(key);
+            match key.parameters() {
+                [] => assert_eq!(Lang::Fr.get(key.name()), Some(value)),
+                _ => assert_eq!(Lang::Fr.get(key.name()), None),
+            }
+        }
+    }
+
+    #[test]
+    fn test_nested() {
+        assert_eq!(Lang::En.auth().login().title(), "Log in");
+        assert_eq!(Lang::Fr.auth().login().title(), "Connexion");
+        assert_eq!(
+            Lang::Fr.auth().login().welcome("John").to_string(),
+            "Bon retour John !"
+        );
+        assert_eq!(Lang::Fr.auth().logout(), "Log out");
+    }
+
+    #[test]
+    fn test_nested_flatten() {
+        use flat::FlatLang;
+
+        assert_eq!(FlatLang::En.auth_login_title(), "Log in");
+        assert_eq!(FlatLang::Fr.auth_login_title(), "Connexion");
+        assert_eq!(
+            FlatLang::Fr.auth_login_welcome("John").to_string(),
+            "Bon retour John !"
+        );
+        assert_eq!(FlatLang::Fr.auth_logout(), "Log out");
+    }
+
+    #[test]
+    fn test_plural() {
+        assert_eq!(Lang::En.items(0), "0 items");
+        assert_eq!(Lang::En.items(1), "1 item");
+        assert_eq!(Lang::En.items(2), "2 items");
+        assert_eq!(Lang::Fr.items(0), "0 élément");
+        assert_eq!(Lang::Fr.items(2), "2 éléments");
+    }
+
+    #[test]
+    fn test_plural_formatted() {
+        assert_eq!(Lang::En.new_messages(1, "John"), "John has a new message");
+        assert_eq!(Lang::En.new_messages(3, "John"), "John has 3 new messages");
+        assert_eq!(
+            Lang::Fr.new_messages(1, "John"),
+            "John a un nouveau message"
+        );
     }
 
     #[test]
@@ -48,20 +216,121 @@ mod tests {
         assert_eq!(Lang::fallback(), Lang::En);
     }
 
+    #[test]
+    fn test_regional() {
+        assert_eq!(Lang::FrCa.hello_name("John").to_string(), "Allô John !");
+    }
+
+    #[test]
+    fn test_fallback_chain() {
+        assert_eq!(Lang::FrCa.hello(), "Bonjour le monde !");
+        assert_eq!(
+            Lang::FrCa.auth().login().welcome("John").to_string(),
+            "Bon retour John !"
+        );
+        assert_eq!(Lang::FrCa.items(2), "2 éléments");
+        assert_eq!(Lang::FrCa.fallback_key(), Lang::En.fallback_key());
+    }
+
     #[test]
     fn test_from_language_id() {
         let en = LanguageId::new("en");
         let fr = LanguageId::new("fr");
+        let fr_ca = LanguageId::new("fr-CA");
         let de = LanguageId::new("de");
 
         assert_eq!(Lang::from_language_id(&en), Some(Lang::En));
         assert_eq!(Lang::from_language_id(&fr), Some(Lang::Fr));
+        assert_eq!(Lang::from_language_id(&fr_ca), Some(Lang::FrCa));
         assert_eq!(Lang::from_language_id(&de), None);
     }
 
+    #[test]
+    fn test_from_language_id_canonicalized() {
+        let fr_ca = LanguageId::new("fr_ca");
+        let invalid = LanguageId::new("invalid");
+
+        assert_eq!(Lang::from_language_id(&fr_ca), Some(Lang::FrCa));
+        assert_eq!(Lang::from_language_id(&invalid), None);
+    }
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(Lang::negotiate("fr-CH, fr;q=0.9, en;q=0.8"), Lang::Fr);
+        assert_eq!(Lang::negotiate("de, fr-ca;q=0.5"), Lang::FrCa);
+        assert_eq!(Lang::negotiate("de-CH, es;q=0.5"), Lang::En);
+    }
+
     #[test]
     fn test_to_language_id() {
         assert_eq!(Lang::En.language_id().value(), "en");
         assert_eq!(Lang::Fr.language_id().value(), "fr");
+        assert_eq!(Lang::FrCa.language_id().value(), "fr-CA");
+    }
+
+    #[test]
+    fn test_all_languages() {
+        assert_eq!(Lang::ALL, [Lang::En, Lang::Fr, Lang::FrCa]);
+        assert_eq!(Lang::COUNT, 3);
+        assert_eq!(<Lang as Language>::all(), Lang::ALL);
+
+        let names: Vec<_> = Lang::ALL.iter().map(Lang::native_name).collect();
+        assert_eq!(names, ["English", "Français", "fr-CA"]);
+    }
+
+    #[test]
+    fn test_coverage_report() {
+        let report = include_str!(concat!(env!("OUT_DIR"), "/rosetta_coverage.json"));
+        assert!(report.contains(r#""fallback": "en""#));
+        assert!(report.contains(r#""fr-CA": {"#));
+
+        let table = include_str!(concat!(env!("OUT_DIR"), "/rosetta_coverage.md"));
+        assert!(table.contains("| fr |"));
+    }
+
+    #[test]
+    fn test_no_std() {
+        use core_only::CoreLang;
+        use std::fmt::Write;
+
+        assert_eq!(CoreLang::Fr.hello(), "Bonjour le monde !");
+        assert_eq!(
+            CoreLang::from_language_id(&LanguageId::borrowed("FR")),
+            Some(CoreLang::Fr)
+        );
+
+        let mut output = String::new();
+        write!(output, "{}", CoreLang::Fr.hello_name("John")).unwrap();
+        write!(output, " / {}", CoreLang::Fr.items(2)).unwrap();
+        write!(output, " / {}", CoreLang::En.new_messages(1, "John")).unwrap();
+        assert_eq!(
+            output,
+            "Bonjour John ! / 2 éléments / John has a new message"
+        );
+
+        let mut output = String::new();
+        let args: &[(&str, &dyn std::fmt::Display)] = &[("name", &"John")];
+        assert_eq!(
+            CoreLang::Fr.write_formatted(&mut output, "hello_name", args),
+            Some(Ok(()))
+        );
+        assert_eq!(
+            CoreLang::Fr.write_formatted(&mut output, "hello_name", &[]),
+            None
+        );
+        assert_eq!(output, "Bonjour John !");
+    }
+
+    #[test]
+    fn test_macro() {
+        use macros::MacroLang;
+
+        assert_eq!(MacroLang::En.hello(), "Hello world!");
+        assert_eq!(
+            MacroLang::Fr.hello_name("John").to_string(),
+            "Bonjour John !"
+        );
+        assert_eq!(MacroLang::Fr.auth().login().title(), "Connexion");
+        assert_eq!(MacroLang::fallback(), MacroLang::En);
     }
 }